@@ -1,6 +1,10 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 use strum::Display;
 
+use crate::components::cron::{history::HistoryEntry, source::CrontabSource};
+
 #[derive(Debug, Clone, PartialEq, Eq, Display, Serialize, Deserialize)]
 pub enum Action {
     Render,
@@ -19,9 +23,47 @@ pub enum Action {
     MoveToTheFirst,
     MoveToTheLast,
     NewRecord,
+    CloneRecord,
     DeleteRecord,
+    ToggleRecord,
+    RunRecord,
+    RecordHistory,
     PassData(Vec<String>),
+    /// The user cron runs the job opened in the popup as.
+    PassJobOwner(String),
     SwitchElement,
+    SwitchCrontabSource(CrontabSource),
+    EditEnvironment,
+    PassEnvVars(Vec<(String, String)>),
+    JobStarted(String),
+    JobStdout(String),
+    JobStderr(String),
+    JobFinished(Option<i32>, Duration),
+    ShowHistory(String),
+    HistoryLoaded(String, Result<Vec<HistoryEntry>, String>),
+    AiAnswer(String, Result<String, String>),
+    PassConflict(String, String),
+    /// What failed, and what that means for the crontab.
+    PassError(String, String),
+    OverwriteCrontab,
+    ToggleDiff,
+    PassReview(String, String),
+    WriteCrontab,
+    CancelReview,
+    SortRecords,
+    ClearSearch,
+    ToggleTimeline,
+    ExportJobs,
+    ImportJobs,
+    ExportToFile(String),
+    PreviewImport(String),
+    TransferResult(Result<Vec<String>, String>),
+    ApplyImport,
+    /// Puts the content of a backup in place of the crontab's, to be saved as any change.
+    RestoreBackup(String),
+    EditGroups,
+    AddMember,
+    RemoveMember,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Display, Serialize, Deserialize, Default, Copy)]
@@ -30,8 +72,28 @@ pub enum Module {
     Home,
     Cron,
     CronPopup,
+    CronSources,
+    CronEnv,
+    CronOutput,
+    CronHistory,
+    CronConflict,
+    CronBackups,
+    CronReview,
+    CronError,
+    CronSearch,
+    CronTransfer,
+    Timers,
+    Anacron,
+    AnacronEdit,
+    AtJobs,
+    AtJobsNew,
     MySql,
     Users,
+    UsersEdit,
+    UsersConfirm,
+    Groups,
+    GroupsEdit,
+    GroupsConfirm,
     Webserver,
     Settings,
 }
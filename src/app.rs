@@ -7,8 +7,12 @@ use tokio::sync::mpsc;
 use crate::{
     action::{Action, Module},
     components::{
-        cron::Cron, cron_popup::CronPopup, home::Home, mysql::MySql, settings::Settings,
-        users::Users, webserver::Webserver, Component,
+        anacron::Anacron, at_jobs::AtJobs, cron::Cron, cron_backups::CronBackups,
+        cron_conflict::CronConflict, cron_env::CronEnv, cron_error::CronError,
+        cron_history::CronHistory, cron_output::CronOutput, cron_popup::CronPopup,
+        cron_review::CronReview, cron_sources::CronSources, cron_transfer::CronTransfer,
+        groups::Groups, home::Home, mysql::MySql, settings::Settings, timers::Timers, users::Users,
+        webserver::Webserver, Component,
     },
     config::Config,
     tui::{Event, Tui},
@@ -33,8 +37,28 @@ pub enum Mode {
     Home,
     Cron,
     CronPopup,
+    CronSources,
+    CronEnv,
+    CronOutput,
+    CronHistory,
+    CronConflict,
+    CronBackups,
+    CronReview,
+    CronError,
+    CronSearch,
+    CronTransfer,
+    Timers,
+    Anacron,
+    AnacronEdit,
+    AtJobs,
+    AtJobsNew,
     MySql,
     Users,
+    UsersEdit,
+    UsersConfirm,
+    Groups,
+    GroupsEdit,
+    GroupsConfirm,
     Webserver,
     Settings,
 }
@@ -49,8 +73,21 @@ impl App {
                 Box::new(Home::new()),
                 Box::new(Cron::new()),
                 Box::new(CronPopup::new()),
+                Box::new(CronSources::new()),
+                Box::new(CronEnv::new()),
+                Box::new(CronOutput::new()),
+                Box::new(CronHistory::new()),
+                Box::new(CronConflict::new()),
+                Box::new(CronBackups::new()),
+                Box::new(CronReview::new()),
+                Box::new(CronError::new()),
+                Box::new(CronTransfer::new()),
+                Box::new(Timers::new()),
+                Box::new(Anacron::new()),
+                Box::new(AtJobs::new()),
                 Box::new(MySql::new()),
                 Box::new(Users::new()),
+                Box::new(Groups::new()),
                 Box::new(Webserver::new()),
                 Box::new(Settings::new()),
             ],
@@ -164,8 +201,28 @@ impl App {
                     Module::Home => self.mode = Mode::Home,
                     Module::Cron => self.mode = Mode::Cron,
                     Module::CronPopup => self.mode = Mode::CronPopup,
+                    Module::CronSources => self.mode = Mode::CronSources,
+                    Module::CronEnv => self.mode = Mode::CronEnv,
+                    Module::CronOutput => self.mode = Mode::CronOutput,
+                    Module::CronHistory => self.mode = Mode::CronHistory,
+                    Module::CronConflict => self.mode = Mode::CronConflict,
+                    Module::CronBackups => self.mode = Mode::CronBackups,
+                    Module::CronReview => self.mode = Mode::CronReview,
+                    Module::CronError => self.mode = Mode::CronError,
+                    Module::CronSearch => self.mode = Mode::CronSearch,
+                    Module::CronTransfer => self.mode = Mode::CronTransfer,
+                    Module::Timers => self.mode = Mode::Timers,
+                    Module::Anacron => self.mode = Mode::Anacron,
+                    Module::AnacronEdit => self.mode = Mode::AnacronEdit,
+                    Module::AtJobs => self.mode = Mode::AtJobs,
+                    Module::AtJobsNew => self.mode = Mode::AtJobsNew,
                     Module::MySql => self.mode = Mode::MySql,
                     Module::Users => self.mode = Mode::Users,
+                    Module::UsersEdit => self.mode = Mode::UsersEdit,
+                    Module::UsersConfirm => self.mode = Mode::UsersConfirm,
+                    Module::Groups => self.mode = Mode::Groups,
+                    Module::GroupsEdit => self.mode = Mode::GroupsEdit,
+                    Module::GroupsConfirm => self.mode = Mode::GroupsConfirm,
                     Module::Webserver => self.mode = Mode::Webserver,
                     Module::Settings => self.mode = Mode::Settings,
                 },
@@ -0,0 +1,55 @@
+use std::io::{self, Write};
+use std::process::{Command, Output, Stdio};
+
+/// Runs `program`, writing `stdin` to it as one line, and fails with everything it printed
+/// when it does not succeed.
+pub fn run_with_output(program: &str, args: &[&str], stdin: Option<&str>) -> io::Result<Output> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        pipe.write_all(input.as_bytes())?;
+        pipe.write_all(b"\n")?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let printed = [
+            String::from_utf8_lossy(&output.stdout).trim(),
+            String::from_utf8_lossy(&output.stderr).trim(),
+        ]
+        .into_iter()
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+        return Err(io::Error::other(format!(
+            "{} failed ({}): {}",
+            program, output.status, printed
+        )));
+    }
+    Ok(output)
+}
+
+/// Runs `program` as [`run_with_output`] does and returns what it printed on stdout.
+pub fn run(program: &str, args: &[&str], stdin: Option<&str>) -> io::Result<String> {
+    let output = run_with_output(program, args, stdin)?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        assert_eq!(run("cat", &[], Some("hello")).unwrap(), "hello\n");
+        let error = run("sh", &["-c", "echo out; echo err >&2; exit 3"], None).unwrap_err();
+        assert_eq!(error.to_string(), "sh failed (exit status: 3): out\nerr");
+    }
+}
@@ -8,11 +8,25 @@ use tokio::sync::mpsc::UnboundedSender;
 
 use crate::{action::Action, config::Config, tui::Event};
 
+pub mod anacron;
+pub mod at_jobs;
 pub mod cron;
+pub mod cron_backups;
+pub mod cron_conflict;
+pub mod cron_env;
+pub mod cron_error;
+pub mod cron_history;
+pub mod cron_output;
 pub mod cron_popup;
+pub mod cron_review;
+pub mod cron_sources;
+pub mod cron_transfer;
+pub mod form;
+pub mod groups;
 pub mod home;
 pub mod mysql;
 pub mod settings;
+pub mod timers;
 pub mod users;
 pub mod webserver;
 
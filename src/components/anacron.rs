@@ -0,0 +1,270 @@
+use std::path::Path;
+
+use color_eyre::Result;
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Layout, Rect},
+    prelude::Frame,
+    text::Line,
+};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::error;
+
+use super::{
+    cron::anacrontab::{AnacronJob, Anacrontab, ANACRONTAB},
+    form::Form,
+    Component,
+};
+use crate::{
+    action::{Action, Module},
+    config::Config,
+    draw::{center, draw_records, Drawable, Record, RecordSelection},
+    style::TableStyles,
+};
+
+impl Drawable for Anacron {}
+const TITLES: [&str; 4] = ["Period (days)", "Delay (min)", "Identifier", "Command"];
+
+/// Lists and edits the jobs of `/etc/anacrontab`.
+#[derive(Default)]
+pub struct Anacron {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    enabled: bool,
+    anacrontab: Anacrontab,
+    selection: RecordSelection,
+    /// The job being edited (`None` for a new one) and its form.
+    editing: Option<(Option<usize>, Form)>,
+    /// The outcome of the last change, or why the file could not be read.
+    message: Option<String>,
+    styles: TableStyles,
+}
+
+impl Anacron {
+    pub fn new() -> Self {
+        Self {
+            styles: TableStyles::new(),
+            ..Self::default()
+        }
+    }
+
+    fn load(&mut self) {
+        self.anacrontab = match Anacrontab::read(Path::new(ANACRONTAB)) {
+            Ok(anacrontab) => anacrontab,
+            Err(e) => {
+                error!("Error reading {}: {}", ANACRONTAB, e);
+                self.message = Some(format!("Error: {}", e));
+                Anacrontab::default()
+            }
+        };
+        self.selection.set_len(self.anacrontab.jobs().count());
+    }
+
+    fn save(&mut self, done: &str) {
+        self.message = Some(match self.anacrontab.write(Path::new(ANACRONTAB)) {
+            Ok(()) => done.to_string(),
+            Err(e) => {
+                error!("Error writing {}: {}", ANACRONTAB, e);
+                format!("Could not save {}: {}", ANACRONTAB, e)
+            }
+        });
+        // Shows the file as it is, whether the write went through or not.
+        self.load();
+    }
+
+    fn edit(&mut self, index: Option<usize>) -> Option<Action> {
+        let job = match index {
+            Some(index) => self.anacrontab.job(index)?.clone(),
+            None => AnacronJob::new("1", "5", "", ""),
+        };
+        let form = Form::new(&[
+            (
+                "Period (days or @daily, @weekly, @monthly, @yearly)",
+                &job.period,
+            ),
+            ("Delay (minutes)", &job.delay),
+            ("Identifier", &job.identifier),
+            ("Command", &job.command),
+        ]);
+        self.editing = Some((index, form));
+        Some(Action::ChangeMode(Module::AnacronEdit))
+    }
+
+    fn confirm(&mut self) -> Option<Action> {
+        let (index, form) = self.editing.as_mut()?;
+        let job = AnacronJob::new(
+            &form.value(0),
+            &form.value(1),
+            &form.value(2),
+            &form.value(3),
+        );
+        let identifier = job.identifier.clone();
+        if let Err(e) = self.anacrontab.set_job(*index, job) {
+            form.error = Some(e);
+            return None;
+        }
+        self.save(&format!("Saved {}", identifier));
+        self.editing = None;
+        Some(Action::ChangeMode(Module::Anacron))
+    }
+
+    fn draw_title(&self, frame: &mut Frame, area: Rect) {
+        let mut title = format!(" Anacrontab: {}", ANACRONTAB);
+        let env_count = self.anacrontab.env_count();
+        if env_count > 0 {
+            title.push_str(&format!("  ·  {} environment variable(s)", env_count));
+        }
+        if let Some(message) = &self.message {
+            title.push_str(&format!("  ·  {}", message));
+        }
+        frame.render_widget(Line::from(title).style(self.styles.header_style), area);
+    }
+
+    fn draw_table(&mut self, frame: &mut Frame, area: Rect) {
+        let records: Vec<Record> = self
+            .anacrontab
+            .jobs()
+            .map(|job| Record {
+                cells: vec![
+                    job.period.clone(),
+                    job.delay.clone(),
+                    job.identifier.clone(),
+                    job.command.clone(),
+                ],
+                dimmed: false,
+                marks: Vec::new(),
+            })
+            .collect();
+        draw_records(
+            frame,
+            area,
+            &TITLES,
+            &records,
+            &mut self.selection,
+            &self.styles,
+        );
+    }
+}
+
+impl Component for Anacron {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if let Some((_, form)) = self.editing.as_mut() {
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Tab => {}
+                _ => form.input(key),
+            }
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::ChangeMode(Module::Anacron) = action {
+            if !self.enabled {
+                self.message = None;
+            }
+            self.editing = None;
+            self.load();
+            self.enabled = true;
+        }
+        if !self.enabled {
+            return Ok(None);
+        }
+        if self.editing.is_some() {
+            match action {
+                Action::SwitchElement => {
+                    if let Some((_, form)) = self.editing.as_mut() {
+                        form.next_field();
+                    }
+                }
+                Action::Confirm => return Ok(self.confirm()),
+                _ => {}
+            }
+            return Ok(None);
+        }
+        match action {
+            Action::ChangeMode(Module::Home) => {
+                self.enabled = false;
+                return Ok(Some(Action::ClearScreen));
+            }
+            Action::Select => {
+                return Ok(self.selection.selected().and_then(|i| self.edit(Some(i))))
+            }
+            Action::NewRecord => return Ok(self.edit(None)),
+            Action::DeleteRecord => {
+                if let Some(job) = self
+                    .selection
+                    .selected()
+                    .and_then(|i| self.anacrontab.remove_job(i))
+                {
+                    self.save(&format!("Removed {}", job.identifier));
+                }
+            }
+            Action::MoveUp => self.selection.previous(),
+            Action::MoveDown => self.selection.next(),
+            Action::MoveToTheFirst => self.selection.first(),
+            Action::MoveToTheLast => self.selection.last(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if self.enabled {
+            let vertical = Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(2),
+            ]);
+            let rects = vertical.split(area);
+
+            self.draw_title(frame, rects[0]);
+            self.draw_table(frame, rects[1]);
+
+            if let Some((index, form)) = self.editing.as_mut() {
+                let title = if index.is_some() {
+                    " Edit anacron job "
+                } else {
+                    " New anacron job "
+                };
+                let popup = center(
+                    frame.area(),
+                    Constraint::Percentage(60),
+                    Constraint::Length(form.height()),
+                );
+                form.draw(frame, popup, title);
+                self.draw_footer(
+                    frame,
+                    rects[2],
+                    vec![
+                        ("<Esc>", "Close without saving"),
+                        ("<Tab>", "Next field"),
+                        ("<Enter>", "Save"),
+                    ],
+                )?;
+            } else {
+                self.draw_footer(
+                    frame,
+                    rects[2],
+                    vec![
+                        ("<Esc>", "Quit"),
+                        ("<↓↑>", "Move up and down"),
+                        ("<Enter>", "Edit"),
+                        ("<n>", "Add new job"),
+                        ("<d>", "Delete"),
+                    ],
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,288 @@
+use color_eyre::Result;
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Text},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::error;
+
+use super::{
+    cron::at::{self, AtJob},
+    form::Form,
+    Component,
+};
+use crate::{
+    action::{Action, Module},
+    config::Config,
+    draw::{center, draw_records, Drawable, Record, RecordSelection},
+    style::TableStyles,
+};
+
+impl Drawable for AtJobs {}
+const TITLES: [&str; 4] = ["Job", "Runs at", "Queue", "User"];
+
+/// Lists the pending `at` jobs, shows what they run, removes them and schedules new ones.
+#[derive(Default)]
+pub struct AtJobs {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    enabled: bool,
+    items: Vec<AtJob>,
+    selection: RecordSelection,
+    /// The commands of the job inspected last, by job id.
+    inspected: Option<(String, Result<String, String>)>,
+    /// The form for a new job, while it is open.
+    scheduling: Option<Form>,
+    /// The outcome of the last command, or why the jobs could not be listed.
+    message: Option<String>,
+    styles: TableStyles,
+}
+
+impl AtJobs {
+    pub fn new() -> Self {
+        Self {
+            styles: TableStyles::new(),
+            ..Self::default()
+        }
+    }
+
+    fn load(&mut self) {
+        self.items = match at::list() {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                error!("Error listing at jobs: {}", e);
+                self.message = Some(format!("Error: {}", e));
+                Vec::new()
+            }
+        };
+        self.selection.set_len(self.items.len());
+    }
+
+    fn selected(&self) -> Option<&AtJob> {
+        self.selection.selected().and_then(|i| self.items.get(i))
+    }
+
+    fn inspect(&mut self) {
+        let Some(id) = self.selected().map(|job| job.id.clone()) else {
+            return;
+        };
+        let commands = at::script(&id)
+            .map(|script| at::script_commands(&script))
+            .map_err(|e| e.to_string());
+        self.inspected = Some((id, commands));
+    }
+
+    fn remove(&mut self) {
+        let Some(id) = self.selected().map(|job| job.id.clone()) else {
+            return;
+        };
+        self.message = Some(match at::remove(&id) {
+            Ok(()) => format!("Removed job {}", id),
+            Err(e) => {
+                error!("Error removing at job: {}", e);
+                format!("Error: {}", e)
+            }
+        });
+        self.load();
+    }
+
+    fn schedule(&mut self) -> Option<Action> {
+        let form = self.scheduling.as_mut()?;
+        let (time, command) = (form.value(0), form.value(1));
+        if time.trim().is_empty() || command.trim().is_empty() {
+            form.error = Some("Both the time and the command are needed".to_string());
+            return None;
+        }
+        match at::schedule(&time, &command) {
+            Ok(report) => {
+                self.message = Some(report);
+                self.scheduling = None;
+                Some(Action::ChangeMode(Module::AtJobs))
+            }
+            Err(e) => {
+                // Mostly a time `at` does not understand, which can be fixed in the form.
+                form.error = Some(e.to_string());
+                None
+            }
+        }
+    }
+
+    fn draw_title(&self, frame: &mut Frame, area: Rect) {
+        let mut title = format!(" Pending at jobs  ·  {} listed", self.items.len());
+        if let Some(message) = &self.message {
+            title.push_str(&format!("  ·  {}", message));
+        }
+        frame.render_widget(Line::from(title).style(self.styles.header_style), area);
+    }
+
+    fn draw_table(&mut self, frame: &mut Frame, area: Rect) {
+        let records: Vec<Record> = self
+            .items
+            .iter()
+            .map(|job| Record {
+                cells: vec![
+                    job.id.clone(),
+                    job.time.clone(),
+                    if job.is_running() {
+                        "= (running)".to_string()
+                    } else {
+                        job.queue.clone()
+                    },
+                    job.user.clone(),
+                ],
+                dimmed: false,
+                marks: Vec::new(),
+            })
+            .collect();
+        draw_records(
+            frame,
+            area,
+            &TITLES,
+            &records,
+            &mut self.selection,
+            &self.styles,
+        );
+    }
+
+    fn draw_commands(&self, frame: &mut Frame, area: Rect) {
+        let Some((id, commands)) = &self.inspected else {
+            return;
+        };
+        let lines = match commands {
+            Ok(commands) => commands
+                .lines()
+                .map(|line| Line::raw(line.to_string()))
+                .collect(),
+            Err(e) => vec![Line::raw(format!("Error: {}", e))],
+        };
+        let pane = Paragraph::new(Text::from(lines))
+            .wrap(Wrap { trim: false })
+            .style(self.styles.row_style.bg(self.styles.normal_row_color))
+            .block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .title(format!(" Commands of job {} ", id)),
+            );
+        frame.render_widget(pane, area);
+    }
+}
+
+impl Component for AtJobs {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if let Some(form) = self.scheduling.as_mut() {
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Tab => {}
+                _ => form.input(key),
+            }
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::ChangeMode(Module::AtJobs) = action {
+            if !self.enabled {
+                self.message = None;
+                self.inspected = None;
+            }
+            self.scheduling = None;
+            self.load();
+            self.enabled = true;
+        }
+        if !self.enabled {
+            return Ok(None);
+        }
+        if let Some(form) = self.scheduling.as_mut() {
+            match action {
+                Action::SwitchElement => form.next_field(),
+                Action::Confirm => return Ok(self.schedule()),
+                _ => {}
+            }
+            return Ok(None);
+        }
+        match action {
+            Action::ChangeMode(Module::Home) => {
+                self.enabled = false;
+                return Ok(Some(Action::ClearScreen));
+            }
+            Action::Select => self.inspect(),
+            Action::NewRecord => {
+                self.scheduling = Some(Form::new(&[
+                    (
+                        "Time (e.g. now + 1 hour, 10:00 tomorrow, 03:00 2025-03-01)",
+                        "now + 1 hour",
+                    ),
+                    ("Command", ""),
+                ]));
+                return Ok(Some(Action::ChangeMode(Module::AtJobsNew)));
+            }
+            Action::DeleteRecord => self.remove(),
+            Action::MoveUp => self.selection.previous(),
+            Action::MoveDown => self.selection.next(),
+            Action::MoveToTheFirst => self.selection.first(),
+            Action::MoveToTheLast => self.selection.last(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if self.enabled {
+            let commands_height = if self.inspected.is_some() { 8 } else { 0 };
+            let vertical = Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(commands_height),
+                Constraint::Length(2),
+            ]);
+            let rects = vertical.split(area);
+
+            self.draw_title(frame, rects[0]);
+            self.draw_table(frame, rects[1]);
+            self.draw_commands(frame, rects[2]);
+
+            if let Some(form) = self.scheduling.as_mut() {
+                let popup = center(
+                    frame.area(),
+                    Constraint::Percentage(60),
+                    Constraint::Length(form.height()),
+                );
+                form.draw(frame, popup, " Schedule a one-off job ");
+                self.draw_footer(
+                    frame,
+                    rects[3],
+                    vec![
+                        ("<Esc>", "Close"),
+                        ("<Tab>", "Next field"),
+                        ("<Enter>", "Schedule"),
+                    ],
+                )?;
+            } else {
+                self.draw_footer(
+                    frame,
+                    rects[3],
+                    vec![
+                        ("<Esc>", "Quit"),
+                        ("<↓↑>", "Move up and down"),
+                        ("<Enter>", "Show commands"),
+                        ("<n>", "Schedule new job"),
+                        ("<d>", "Remove"),
+                    ],
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,50 +1,105 @@
+pub mod ai;
+pub mod anacrontab;
+pub mod at;
+pub mod backup;
+pub mod crontab;
+pub mod diff;
+pub mod history;
+pub mod lint;
+pub mod natural;
+pub mod runner;
+pub mod source;
+pub mod timeline;
+pub mod transfer;
 pub mod utils;
+pub mod view;
+pub mod wrapper;
 
+use std::path::Path;
+
+use chrono::Utc;
 use color_eyre::Result;
 use ratatui::{
-    crossterm::event::{MouseEvent, MouseEventKind},
-    layout::{Constraint, Layout, Margin, Rect},
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind},
+    layout::{Constraint, Layout, Rect},
     prelude::Frame,
-    text::Text,
-    widgets::{
-        Cell, HighlightSpacing, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table,
-        TableState,
-    },
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph},
 };
-use tokio::sync::mpsc::UnboundedSender;
+use tokio::{sync::mpsc::UnboundedSender, task::JoinHandle};
 use tracing::error;
 
 use super::Component;
 use crate::{
     action::{Action, Module},
     config::Config,
-    draw::Drawable,
+    draw::{draw_records, Drawable, Record, RecordSelection, RECORD_HEIGHT},
     style::TableStyles,
 };
-use utils::{constraint_len_calculator, from_crontab, get_next_execution, save_to_crontab};
+use backup::Backups;
+use crontab::Crontab;
+use lint::{lint_crontab, Lint};
+use source::CrontabSource;
+use timeline::{Timeline, TimelineSpan};
+use transfer::{CrontabFile, ImportPlan};
+use utils::{from_crontab, overwrite_crontab, save_to_crontab, SaveError};
+use view::{visible_jobs, SortOrder};
+use wrapper::Wrapper;
 
 impl Drawable for Cron {}
-const ITEM_HEIGHT: usize = 3;
+/// Slots of the timeline with this many jobs or more are highlighted as busy.
+const BUSY_SLOT: usize = 3;
+/// What a failed save leaves behind, which the error dialog tells.
+const NOT_SAVED: &str = "The change was not saved; the table shows the crontab as it is.";
 
 #[derive(Default)]
 pub struct Cron {
     command_tx: Option<UnboundedSender<Action>>,
     config: Config,
     enabled: bool,
-    mouse: bool,
-    state: TableState,
-    items: Vec<CronJob>,
-    longest_item_lens: (u16, u16, u16),
-    scroll_state: ScrollbarState,
+    focused: bool,
+    source: CrontabSource,
+    selection: RecordSelection,
+    crontab: Crontab,
+    /// Indices of the jobs shown in the table, after filtering and sorting.
+    rows: Vec<usize>,
+    /// Lint findings of every job, in crontab order.
+    lints: Vec<Vec<Lint>>,
+    search: String,
+    searching: bool,
+    sort: SortOrder,
+    /// Shown instead of the table when set.
+    timeline_span: Option<TimelineSpan>,
+    timeline: Option<Timeline>,
+    load_error: Option<String>,
+    running_job: Option<JoinHandle<()>>,
+    /// Changes that could not be saved because the crontab changed on disk meanwhile.
+    conflict: Option<(CrontabSource, Crontab)>,
+    /// Changes waiting for review, and the actions that reopen their editor on cancel.
+    review: Option<(Crontab, Vec<Action>)>,
+    /// The import last previewed, applied once confirmed.
+    import: Option<ImportPlan>,
+    /// User of the job being cloned, which the clone keeps in system crontabs.
+    clone_user: Option<String>,
     styles: TableStyles,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct CronJob {
     pub cron_notation: String,
+    /// The command, without the wrapper tuixel puts around it.
     pub job: String,
+    pub wrapper: Wrapper,
     pub job_description: String,
     pub next_execution: String,
+    pub user: String,
+    /// Kept in the crontab but commented out, so cron never runs it.
+    pub disabled: bool,
+    /// The zone set by the last `CRON_TZ` line above the job; empty when there is none.
+    pub timezone: String,
+    raw: Option<String>,
+    raw_description: Option<String>,
 }
 
 impl CronJob {
@@ -58,145 +113,389 @@ impl CronJob {
 
     pub fn new(cron_job: CronJob) -> Self {
         Self {
-            cron_notation: cron_job.cron_notation,
-            job: cron_job.job,
-            job_description: cron_job.job_description,
-            next_execution: cron_job.next_execution,
+            raw: None,
+            raw_description: None,
+            ..cron_job
         }
     }
+
+    /// The command as the crontab has it, wrapper included.
+    pub fn command(&self) -> String {
+        self.wrapper.wrap(&self.job)
+    }
 }
 
 impl Cron {
     pub fn new() -> Self {
-        let cron_jobs_vec = vec![];
-        let scroll_position = if cron_jobs_vec.is_empty() {
-            0
-        } else {
-            (cron_jobs_vec.len() - 1) * ITEM_HEIGHT
-        };
         Self {
             command_tx: None,
             config: Config::default(),
             enabled: false,
-            mouse: true,
-            state: TableState::default().with_selected(0),
-            longest_item_lens: constraint_len_calculator(&cron_jobs_vec),
-            scroll_state: ScrollbarState::new(scroll_position),
+            focused: true,
+            source: CrontabSource::default(),
+            selection: RecordSelection::default(),
+            crontab: Crontab::default(),
+            rows: Vec::new(),
+            lints: Vec::new(),
+            search: String::new(),
+            searching: false,
+            sort: SortOrder::default(),
+            timeline_span: None,
+            timeline: None,
+            load_error: None,
+            running_job: None,
+            conflict: None,
+            review: None,
+            import: None,
+            clone_user: None,
             styles: TableStyles::new(),
-            items: cron_jobs_vec,
         }
     }
 
-    fn draw_table(&mut self, frame: &mut Frame, area: Rect) {
-        let header = ["Cron Notation", "Next Execution", "Description"]
-            .into_iter()
-            .map(|title| Cell::from(Text::from(format!("\n{}\n", title))))
-            .collect::<Row>()
-            .style(self.styles.header_style)
-            .height(3);
-
-        let rows = self.items.iter().enumerate().map(|(i, data)| {
-            let color = if i % 2 == 0 {
-                self.styles.normal_row_color
-            } else {
-                self.styles.alt_row_color
-            };
-            let item = data.ref_array();
-            item.into_iter()
-                .map(|content| Cell::from(Text::from(format!("\n{content}\n"))))
-                .collect::<Row>()
-                .style(self.styles.row_style.bg(color))
-                .height(ITEM_HEIGHT.try_into().unwrap())
-        });
+    fn load(&mut self) {
+        let selected = self.selected_key();
+        match from_crontab(&self.source, &self.config.settings.cron) {
+            Ok(crontab) => {
+                self.crontab = crontab;
+                self.load_error = None;
+            }
+            Err(err) => {
+                error!("Error reading crontab: {}", err);
+                self.crontab = Crontab::default();
+                self.load_error = Some(err.to_string());
+            }
+        }
+        self.refresh_rows(selected);
+    }
 
-        let bar = " ▌ ";
-        self.longest_item_lens = constraint_len_calculator(&self.items);
-        if self.longest_item_lens.0 < "Cron Notation".len() as u16 {
-            self.longest_item_lens.0 = "Cron Notation".len() as u16;
-        }
-        let table = Table::new(
-            rows,
-            [
-                Constraint::Length(self.longest_item_lens.0 + 8),
-                Constraint::Min(self.longest_item_lens.1 + 1),
-                Constraint::Min(self.longest_item_lens.2),
-            ],
-        )
-        .header(header)
-        .row_highlight_style(self.styles.selected_row_style)
-        .style(
-            self.styles
-                .row_style
-                .bg(if (self.items.len() + 1) % 2 == 0 {
-                    self.styles.alt_row_color
-                } else {
-                    self.styles.normal_row_color
-                }),
-        )
-        .highlight_symbol(Text::from(vec!["".into(), bar.into(), "".into()]))
-        .highlight_spacing(HighlightSpacing::Always);
-
-        frame.render_stateful_widget(table, area, &mut self.state);
+    /// Index into the crontab's jobs of the selected row.
+    fn selected_index(&self) -> Option<usize> {
+        self.selection
+            .selected()
+            .and_then(|row| self.rows.get(row).copied())
     }
 
-    fn draw_scrollbar(&mut self, frame: &mut Frame, area: Rect) {
-        let scrollbar = Scrollbar::default()
-            .orientation(ScrollbarOrientation::VerticalRight)
-            .begin_symbol(None)
-            .end_symbol(None)
-            .style(self.styles.scrollbar_style);
-
-        frame.render_stateful_widget(
-            scrollbar,
-            area.inner(Margin {
-                vertical: 1,
-                horizontal: 1,
-            }),
-            &mut self.scroll_state,
-        );
+    fn selected_job(&self) -> Option<&CronJob> {
+        self.selected_index()
+            .and_then(|index| self.crontab.job(index))
     }
 
-    fn next_row(&mut self) {
-        let i = match self.state.selected() {
-            Some(i) => {
-                if i >= self.items.len() - 1 {
-                    0
-                } else {
-                    i + 1
-                }
-            }
-            None => 0,
+    /// Identifies the selected job across reloads, which may move it around.
+    fn selected_key(&self) -> Option<(String, String)> {
+        self.selected_job()
+            .map(|job| (job.cron_notation.clone(), job.job.clone()))
+    }
+
+    /// Filters and sorts the rows again, keeping `selected` selected if it is still listed.
+    fn refresh_rows(&mut self, selected: Option<(String, String)>) {
+        let jobs: Vec<&CronJob> = self.crontab.jobs().collect();
+        self.rows = visible_jobs(&jobs, &self.search, self.sort);
+        self.lints = lint_crontab(&self.crontab);
+        self.timeline = None;
+
+        // Keeps the selected row where it is, or moves it to the last one if that is gone.
+        self.selection.set_len(self.rows.len());
+        let position = selected.and_then(|(notation, command)| {
+            self.rows
+                .iter()
+                .position(|&i| jobs[i].cron_notation == notation && jobs[i].job == command)
+        });
+        if let Some(row) = position {
+            self.selection.select(row);
+        }
+    }
+
+    /// What the popup gets to edit the job at `index`, or a copy of it as a new job when `clone`.
+    fn popup_data(&self, index: usize, clone: bool) -> Vec<String> {
+        let Some(job) = self.crontab.job(index) else {
+            return Vec::new();
         };
-        self.state.select(Some(i));
-        self.scroll_state = self.scroll_state.position(i * ITEM_HEIGHT);
+        let popup_index = if clone { -1 } else { index as i64 };
+        vec![
+            popup_index.to_string(),
+            job.cron_notation.to_string(),
+            job.command(),
+            job.job_description.to_string(),
+            job.timezone.to_string(),
+        ]
+        .into_iter()
+        .chain(self.lints[index].iter().map(Lint::to_string))
+        .collect()
     }
 
-    fn previous_row(&mut self) {
-        let i = match self.state.selected() {
-            Some(i) => {
-                if i == 0 {
-                    self.items.len() - 1
-                } else {
-                    i - 1
-                }
+    /// The user cron runs `job` as: the owner of the crontab, or the user column of a system
+    /// crontab, where new jobs go to root. `None` for the crontab of the user running tuixel.
+    fn job_owner<'a>(&'a self, job: Option<&'a CronJob>) -> Option<&'a str> {
+        match &self.source {
+            CrontabSource::CurrentUser => None,
+            CrontabSource::User(user) => Some(user.as_str()),
+            CrontabSource::System(_) => Some(job.map_or("root", |job| job.user.as_str())),
+        }
+    }
+
+    /// Opens the popup on `data`, telling it who the job runs as.
+    fn open_popup(&self, data: Vec<String>, job: Option<&CronJob>) -> Action {
+        let owner = self
+            .job_owner(job)
+            .map(str::to_string)
+            .unwrap_or_else(source::current_username);
+        let tx = self.command_tx.clone().unwrap();
+        tx.send(Action::PassJobOwner(owner)).unwrap();
+        tx.send(Action::PassData(data)).unwrap();
+        Action::ChangeMode(Module::CronPopup)
+    }
+
+    fn backups(&self) -> Backups {
+        Backups::for_source(&self.source, self.config.settings.cron.backups)
+    }
+
+    /// Saves the changes, or asks for a review of them first when `cron.review` is on.
+    /// `reopen` brings back the editor the changes came from if the review is cancelled.
+    fn submit(&mut self, reopen: Vec<Action>) {
+        if !self.config.settings.cron.review || !self.crontab.is_modified() {
+            self.save();
+            return;
+        }
+        let tx = self.command_tx.clone().unwrap();
+        tx.send(Action::PassReview(
+            self.crontab.saved_content().to_string(),
+            self.crontab.render(),
+        ))
+        .unwrap();
+        tx.send(Action::ChangeMode(Module::CronReview)).unwrap();
+        self.review = Some((self.crontab.clone(), reopen));
+    }
+
+    fn save(&mut self) {
+        let backups = self.backups();
+        match save_to_crontab(&self.source, &mut self.crontab, &backups) {
+            Ok(()) => {}
+            Err(SaveError::Conflict(theirs)) => {
+                let tx = self.command_tx.clone().unwrap();
+                tx.send(Action::PassConflict(self.crontab.render(), theirs))
+                    .unwrap();
+                tx.send(Action::ChangeMode(Module::CronConflict)).unwrap();
+                self.conflict = Some((self.source.clone(), self.crontab.clone()));
             }
-            None => 0,
-        };
-        self.state.select(Some(i));
-        self.scroll_state = self.scroll_state.position(i * ITEM_HEIGHT);
+            Err(err) => {
+                error!("Error saving to crontab: {}", err);
+                let selected = self.selected_key();
+                self.crontab.revert(&self.config.settings.cron);
+                self.refresh_rows(selected);
+                self.show_error(format!("Could not save the crontab: {}", err), NOT_SAVED);
+            }
+        }
     }
 
-    fn first_row(&mut self) {
-        self.state.select(Some(0));
-        self.scroll_state = self.scroll_state.position(0);
+    /// Opens the error dialog on `message`, with `note` telling what came of the action.
+    fn show_error(&self, message: String, note: &str) {
+        let tx = self.command_tx.clone().unwrap();
+        tx.send(Action::PassError(message, note.to_string()))
+            .unwrap();
+        tx.send(Action::ChangeMode(Module::CronError)).unwrap();
     }
 
-    fn last_row(&mut self) {
-        if !self.items.is_empty() {
-            let last_index = self.items.len() - 1;
-            self.state.select(Some(last_index));
-            self.scroll_state = self.scroll_state.position(last_index * ITEM_HEIGHT);
+    fn draw_title(&self, frame: &mut Frame, area: Rect) {
+        let mut title = format!(" Crontab: {}", self.source.label());
+        if let Some(err) = &self.load_error {
+            title.push_str(&format!("  ·  Error: {}", err));
+        } else if self.crontab.job_count() == 0 {
+            title.push_str("  ·  No jobs yet");
+        }
+        let env_count = self.crontab.env_vars().count();
+        if env_count > 0 {
+            title.push_str(&format!("  ·  {} environment variable(s)", env_count));
         }
+        if self.searching {
+            title.push_str(&format!("  ·  Search: {}▏", self.search));
+        } else if !self.search.is_empty() {
+            title.push_str(&format!("  ·  Filter: {}", self.search));
+        }
+        if !self.search.is_empty() {
+            title.push_str(&format!(
+                " ({} of {})",
+                self.rows.len(),
+                self.crontab.job_count()
+            ));
+        }
+        if self.sort != SortOrder::Crontab {
+            title.push_str(&format!("  ·  Sorted by {}", self.sort.label()));
+        }
+        frame.render_widget(Line::from(title).style(self.styles.header_style), area);
+    }
+
+    fn draw_table(&mut self, frame: &mut Frame, area: Rect) {
+        let system = self.source.is_system();
+        let mut titles = vec!["Cron Notation", "Next Execution", "Description"];
+        if system {
+            titles.insert(1, "User");
+        }
+        let records: Vec<Record> = self
+            .rows
+            .iter()
+            .filter_map(|&index| self.crontab.job(index).map(|job| (index, job)))
+            .map(|(index, job)| {
+                let mut cells: Vec<String> = job.ref_array().map(String::clone).to_vec();
+                if system {
+                    cells.insert(1, job.user.clone());
+                }
+                let mut marks = Vec::new();
+                if self.lints.get(index).is_some_and(|lints| !lints.is_empty()) {
+                    marks.push((
+                        0,
+                        Span::styled(" ⚠", Style::default().fg(Color::LightYellow)),
+                    ));
+                }
+                if !job.wrapper.is_empty() {
+                    marks.push((
+                        cells.len() - 1,
+                        Span::styled(
+                            format!(" [{}]", job.wrapper.summary()),
+                            Style::default().add_modifier(Modifier::DIM),
+                        ),
+                    ));
+                }
+                Record {
+                    cells,
+                    dimmed: job.disabled,
+                    marks,
+                }
+            })
+            .collect();
+        draw_records(
+            frame,
+            area,
+            &titles,
+            &records,
+            &mut self.selection,
+            &self.styles,
+        );
+    }
+
+    /// Draws when the listed jobs fire, per minute over the next 24 hours or per hour over the
+    /// next 7 days, so that jobs piling up on the same slot stand out.
+    fn draw_timeline(&mut self, frame: &mut Frame, area: Rect, span: TimelineSpan) {
+        let timezone = utils::parse_timezone(&self.config.settings.cron.timezone)
+            .unwrap_or(chrono_tz::Tz::UTC);
+        let now = Utc::now().with_timezone(&timezone);
+        let jobs: Vec<&CronJob> = self
+            .rows
+            .iter()
+            .filter_map(|&index| self.crontab.job(index))
+            .collect();
+        // The layout only changes with the jobs or when a new hour starts.
+        let stale = self.timeline.as_ref().is_none_or(|timeline| {
+            timeline.span != span || (now - timeline.start).num_minutes() >= 60
+        });
+        if stale {
+            self.timeline = Some(Timeline::new(&jobs, span, now));
+        }
+        let Some(timeline) = self.timeline.as_ref() else {
+            return;
+        };
+
+        let slot_style = |count: usize| match count {
+            0 => Style::default().fg(Color::DarkGray),
+            1 => Style::default().fg(Color::LightGreen),
+            count if count < BUSY_SLOT => Style::default().fg(Color::LightYellow),
+            _ => Style::default()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+        };
+        let mut lines: Vec<Line> = Vec::new();
+        match span {
+            TimelineSpan::Day => {
+                lines.push(Line::raw(format!(
+                    "       {}",
+                    (0..60)
+                        .step_by(5)
+                        .map(|minute| format!("{:<5}", minute))
+                        .collect::<String>()
+                )));
+                for (hour, slots) in timeline.slots.chunks(60).enumerate() {
+                    let mut spans = vec![Span::raw(format!(
+                        "{}  ",
+                        timeline.slot_start(hour * 60).format("%H:%M")
+                    ))];
+                    spans.extend(slots.iter().map(|jobs| {
+                        let symbol = match jobs.len() {
+                            0 => "·".to_string(),
+                            count @ 1..=9 => count.to_string(),
+                            _ => "+".to_string(),
+                        };
+                        Span::styled(symbol, slot_style(jobs.len()))
+                    }));
+                    lines.push(Line::from(spans));
+                }
+            }
+            TimelineSpan::Week => {
+                lines.push(Line::raw(format!(
+                    "        {}",
+                    (0..24)
+                        .map(|slot| timeline.slot_start(slot).format("%H ").to_string())
+                        .collect::<String>()
+                )));
+                for (day, slots) in timeline.slots.chunks(24).enumerate() {
+                    let mut spans = vec![Span::raw(format!(
+                        "{}  ",
+                        timeline.slot_start(day * 24).format("%a %d")
+                    ))];
+                    spans.extend(slots.iter().map(|jobs| {
+                        let symbol = match jobs.len() {
+                            0 => " · ".to_string(),
+                            count => format!("{:>2} ", count.min(99)),
+                        };
+                        Span::styled(symbol, slot_style(jobs.len()))
+                    }));
+                    lines.push(Line::from(spans));
+                }
+            }
+        }
+
+        lines.push(Line::raw(""));
+        let busiest = timeline.busiest(2, 5);
+        if busiest.is_empty() {
+            lines.push(Line::raw("No two jobs fire in the same slot."));
+        } else {
+            lines.push(Line::styled("Busiest slots:", self.styles.header_style));
+        }
+        let slot_format = match span {
+            TimelineSpan::Day => "%H:%M",
+            TimelineSpan::Week => "%a %H:00",
+        };
+        for slot in busiest {
+            let names: Vec<&str> = timeline.slots[slot]
+                .iter()
+                .map(|&index| {
+                    let job = jobs[index];
+                    if job.job_description.is_empty() {
+                        job.job.as_str()
+                    } else {
+                        job.job_description.as_str()
+                    }
+                })
+                .collect();
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{}  {} jobs",
+                        timeline.slot_start(slot).format(slot_format),
+                        names.len()
+                    ),
+                    slot_style(names.len()),
+                ),
+                Span::raw(format!(": {}", names.join(", "))),
+            ]));
+        }
+
+        let title = match span {
+            TimelineSpan::Day => format!(" Jobs per minute, next 24 hours ({}) ", timezone),
+            TimelineSpan::Week => format!(" Jobs per hour, next 7 days ({}) ", timezone),
+        };
+        let timeline = Paragraph::new(Text::from(lines))
+            .style(self.styles.row_style.bg(self.styles.normal_row_color))
+            .block(Block::default().borders(Borders::TOP).title(title));
+        frame.render_widget(timeline, area);
     }
 }
 
@@ -211,24 +510,40 @@ impl Component for Cron {
         Ok(())
     }
 
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.enabled && self.searching {
+            let selected = self.selected_key();
+            match key.code {
+                KeyCode::Backspace => {
+                    self.search.pop();
+                }
+                KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.search.push(c);
+                }
+                _ => return Ok(None),
+            }
+            self.refresh_rows(selected);
+        }
+        Ok(None)
+    }
+
     fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<Option<Action>> {
-        if self.enabled && self.mouse {
-            let start_row: u16 = 3;
-            let row_height: u16 = ITEM_HEIGHT as u16;
+        if self.enabled && self.focused {
+            let start_row: u16 = 4;
+            let row_height: u16 = RECORD_HEIGHT as u16;
 
-            let table_height = self.items.len();
+            let table_height = self.rows.len();
 
             if mouse.row >= start_row && mouse.row < start_row + (table_height as u16 * row_height)
             {
                 let selected_index = ((mouse.row - start_row) / row_height) as usize;
 
-                if selected_index < self.items.len() {
-                    self.state.select(Some(selected_index));
+                if selected_index < table_height {
+                    self.selection.select(selected_index);
 
                     if let MouseEventKind::Up(_) = mouse.kind {
                         let tx = self.command_tx.clone().unwrap();
                         tx.send(Action::Select).unwrap();
-                        self.mouse = false;
                     }
                 }
             }
@@ -238,55 +553,147 @@ impl Component for Cron {
     }
 
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::ChangeMode(module) = action {
+            self.focused = module == Module::Cron || module == Module::CronSearch;
+            self.searching = module == Module::CronSearch;
+        }
+        if let Action::SwitchCrontabSource(ref source) = action {
+            self.source = source.clone();
+            self.conflict = None;
+            self.review = None;
+            self.import = None;
+            self.search.clear();
+            self.selection.first();
+        }
         if let Action::ChangeMode(Module::Cron) = action {
-            self.mouse = true;
-            let cron_jobs_vec =
-                from_crontab(&self.config.settings.cron.timezone).unwrap_or_else(|err| {
-                    tracing::error!("Error reading crontab: {}", err);
-                    vec![CronJob {
-                        cron_notation: format!("Error: {}", err),
-                        job: String::new(),
-                        job_description: String::new(),
-                        next_execution: String::new(),
-                    }]
-                });
-            let scroll_position = if cron_jobs_vec.is_empty() {
-                0
-            } else {
-                (cron_jobs_vec.len() - 1) * ITEM_HEIGHT
-            };
-            self.items = cron_jobs_vec;
-            self.scroll_state = ScrollbarState::new(scroll_position);
+            // Closing the output pane stops a job started with "run now".
+            if let Some(job) = self.running_job.take() {
+                job.abort();
+            }
+            self.load();
             self.enabled = true;
         }
+        // Data sent back by the popups; the table's own requests to them are ignored.
         if let Action::PassData(ref cron) = action {
-            if !cron.is_empty() {
+            if !cron.is_empty() && !self.focused {
+                let selected = self.selected_key();
                 let index: i32 = cron[0].parse().unwrap();
+                let timezone = cron.get(4).map(String::as_str).unwrap_or_default();
+                let (command, wrapper) = Wrapper::unwrap(&cron[2]);
                 if index == -1 {
-                    self.items.push(CronJob::new(CronJob {
+                    self.crontab.push_job(CronJob::new(CronJob {
                         cron_notation: cron[1].clone(),
-                        job: cron[2].clone(),
+                        job: command,
+                        wrapper,
                         job_description: cron[3].clone(),
-                        next_execution: get_next_execution(
-                            &cron[1],
-                            &self.config.settings.cron.timezone,
-                        ),
+                        user: match &self.clone_user {
+                            _ if !self.source.is_system() => String::new(),
+                            Some(user) => user.clone(),
+                            None => "root".to_string(),
+                        },
+                        ..CronJob::default()
                     }));
-                    save_to_crontab(&self.items).unwrap_or_else(|err| {
-                        error!("Error saving to crontab: {}", err);
-                    });
-                } else {
-                    self.items[index as usize].cron_notation = cron[1].clone();
-                    self.items[index as usize].job = cron[2].clone();
-                    self.items[index as usize].job_description = cron[3].clone();
-                    self.items[index as usize].next_execution =
-                        get_next_execution(&cron[1], &self.config.settings.cron.timezone);
-                    save_to_crontab(&self.items).unwrap_or_else(|err| {
-                        error!("Error saving to crontab: {}", err);
-                    });
+                    let last = self.crontab.job_count() - 1;
+                    self.crontab.set_job_timezone(last, timezone);
+                } else if let Some(job) = self.crontab.job_mut(index as usize) {
+                    job.cron_notation = cron[1].clone();
+                    job.job = command;
+                    job.wrapper = wrapper;
+                    job.job_description = cron[3].clone();
+                    self.crontab.set_job_timezone(index as usize, timezone);
+                }
+                self.crontab.update_schedule();
+                self.refresh_rows(selected);
+                self.submit(vec![
+                    Action::PassData(cron.clone()),
+                    Action::ChangeMode(Module::CronPopup),
+                ]);
+            }
+        }
+        if let Action::OverwriteCrontab = action {
+            if let Some((source, mut crontab)) = self.conflict.take() {
+                let backups = Backups::for_source(&source, self.config.settings.cron.backups);
+                if let Err(err) = overwrite_crontab(&source, &mut crontab, &backups) {
+                    error!("Error saving to crontab: {}", err);
+                    // The unsaved changes are dropped, so the table shows what is on disk.
+                    self.load();
+                    self.show_error(format!("Could not save the crontab: {}", err), NOT_SAVED);
+                }
+            }
+        }
+        if let Action::WriteCrontab = action {
+            if let Some((crontab, _)) = self.review.take() {
+                // Back to the table first, so a conflict dialog opened by the save stays open.
+                let tx = self.command_tx.clone().unwrap();
+                tx.send(Action::ChangeMode(Module::Cron)).unwrap();
+                let selected = self.selected_key();
+                self.crontab = crontab;
+                self.refresh_rows(selected);
+                self.save();
+            }
+        }
+        if let Action::CancelReview = action {
+            if let Some((_, reopen)) = self.review.take() {
+                // The editors take their data back while the table counts as focused,
+                // so it is not applied here again.
+                self.focused = true;
+                let tx = self.command_tx.clone().unwrap();
+                for action in reopen {
+                    tx.send(action).unwrap();
                 }
             }
         }
+        if let Action::ExportToFile(ref path) = action {
+            let file = CrontabFile::from_crontab(&self.crontab);
+            let result = file
+                .write(Path::new(path))
+                .map(|()| vec![format!("Exported {} job(s) to {}", file.jobs.len(), path)]);
+            let tx = self.command_tx.clone().unwrap();
+            tx.send(Action::TransferResult(result)).unwrap();
+        }
+        if let Action::PreviewImport(ref path) = action {
+            let result = CrontabFile::read(Path::new(path)).and_then(|file| {
+                let plan = ImportPlan::new(
+                    &self.crontab,
+                    &file,
+                    self.source.is_system(),
+                    &self.config.settings.cron,
+                )?;
+                let summary = plan.summary(&self.crontab);
+                self.import = Some(plan);
+                Ok(summary)
+            });
+            let tx = self.command_tx.clone().unwrap();
+            tx.send(Action::TransferResult(result)).unwrap();
+        }
+        if let Action::ApplyImport = action {
+            // Back to the table first, as for a review, so that dialogs opened by the save stay.
+            let tx = self.command_tx.clone().unwrap();
+            tx.send(Action::ChangeMode(Module::Cron)).unwrap();
+            if let Some(plan) = self.import.take().filter(|plan| !plan.is_empty()) {
+                let selected = self.selected_key();
+                plan.apply(&mut self.crontab);
+                self.refresh_rows(selected);
+                self.submit(vec![Action::ChangeMode(Module::Cron)]);
+            }
+        }
+        if let Action::RestoreBackup(ref content) = action {
+            let tx = self.command_tx.clone().unwrap();
+            tx.send(Action::ChangeMode(Module::Cron)).unwrap();
+            let selected = self.selected_key();
+            self.crontab.replace(content, &self.config.settings.cron);
+            self.refresh_rows(selected);
+            self.submit(vec![Action::ChangeMode(Module::CronBackups)]);
+        }
+        if let Action::PassEnvVars(ref vars) = action {
+            if !self.focused {
+                self.crontab.set_env_vars(vars);
+                self.submit(vec![
+                    Action::PassEnvVars(vars.clone()),
+                    Action::ChangeMode(Module::CronEnv),
+                ]);
+            }
+        }
         if self.enabled {
             let tx = self.command_tx.clone().unwrap();
             match action {
@@ -294,46 +701,114 @@ impl Component for Cron {
                     self.enabled = false;
                     return Ok(Some(Action::ClearScreen));
                 }
+                _ if !self.focused => {}
                 Action::NewRecord => {
-                    tx.send(Action::PassData(vec![])).unwrap();
-                    self.mouse = false;
-                    return Ok(Some(Action::ChangeMode(Module::CronPopup)));
+                    self.clone_user = None;
+                    return Ok(Some(self.open_popup(vec![], None)));
+                }
+                Action::CloneRecord => {
+                    if let Some(index) = self.selected_index() {
+                        self.clone_user = self.crontab.job(index).map(|job| job.user.clone());
+                        let data = self.popup_data(index, true);
+                        return Ok(Some(self.open_popup(data, self.crontab.job(index))));
+                    }
                 }
                 Action::DeleteRecord => {
-                    let index = self.state.selected().unwrap();
-                    self.items.remove(index);
-                    save_to_crontab(&self.items).unwrap_or_else(|err| {
-                        error!("Error saving to crontab: {}", err);
-                    });
+                    if let Some(index) = self.selected_index() {
+                        if self.crontab.remove_job(index).is_some() {
+                            self.refresh_rows(None);
+                            self.submit(vec![Action::ChangeMode(Module::Cron)]);
+                        }
+                    }
+                }
+                Action::ToggleRecord => {
+                    let selected = self.selected_key();
+                    if let Some(job) = self
+                        .selected_index()
+                        .and_then(|index| self.crontab.job_mut(index))
+                    {
+                        job.disabled = !job.disabled;
+                        self.crontab.update_schedule();
+                        self.refresh_rows(selected);
+                        self.submit(vec![Action::ChangeMode(Module::Cron)]);
+                    }
+                }
+                Action::ToggleTimeline => {
+                    self.timeline_span = match self.timeline_span {
+                        None => Some(TimelineSpan::Day),
+                        Some(TimelineSpan::Day) => Some(TimelineSpan::Week),
+                        Some(TimelineSpan::Week) => None,
+                    };
+                    self.timeline = None;
+                }
+                Action::SortRecords => {
+                    let selected = self.selected_key();
+                    self.sort = self.sort.next();
+                    self.refresh_rows(selected);
+                }
+                Action::ClearSearch => {
+                    let selected = self.selected_key();
+                    self.search.clear();
+                    self.refresh_rows(selected);
+                    return Ok(Some(Action::ChangeMode(Module::Cron)));
+                }
+                Action::RunRecord => {
+                    if let Some(job) = self.selected_job() {
+                        // As cron would, run the job as the user owning it.
+                        let user = match runner::run_as(self.job_owner(Some(job))) {
+                            Ok(user) => user,
+                            Err(message) => {
+                                self.show_error(message, "The job was not run.");
+                                return Ok(None);
+                            }
+                        };
+                        let env: Vec<(String, String)> = self
+                            .crontab
+                            .env_vars()
+                            .map(|var| (var.name.clone(), var.value.clone()))
+                            .collect();
+                        // Wrapped as in the crontab, so the lock and time limit apply as well.
+                        let command = job.command();
+                        tx.send(Action::JobStarted(command.clone())).unwrap();
+                        self.running_job =
+                            Some(runner::spawn_job(&command, &env, user.as_deref(), tx));
+                        return Ok(Some(Action::ChangeMode(Module::CronOutput)));
+                    }
+                }
+                Action::RecordHistory => {
+                    if let Some(job) = self.selected_job() {
+                        // Cron logs the command as written in the crontab.
+                        tx.send(Action::ShowHistory(job.command())).unwrap();
+                        return Ok(Some(Action::ChangeMode(Module::CronHistory)));
+                    }
                 }
                 Action::Select => {
-                    if !self.items.is_empty() {
-                        tx.send(Action::PassData(vec![
-                            self.state.selected().unwrap().to_string(),
-                            self.items[self.state.selected().unwrap()]
-                                .cron_notation
-                                .to_string(),
-                            self.items[self.state.selected().unwrap()].job.to_string(),
-                            self.items[self.state.selected().unwrap()]
-                                .job_description
-                                .to_string(),
-                        ]))
-                        .unwrap();
-                        self.mouse = false;
-                        return Ok(Some(Action::ChangeMode(Module::CronPopup)));
+                    if let Some(index) = self.selected_index() {
+                        let data = self.popup_data(index, false);
+                        return Ok(Some(self.open_popup(data, self.crontab.job(index))));
                     }
                 }
+                Action::EditEnvironment => {
+                    tx.send(Action::PassEnvVars(
+                        self.crontab
+                            .env_vars()
+                            .map(|var| (var.name.clone(), var.value.clone()))
+                            .collect(),
+                    ))
+                    .unwrap();
+                    return Ok(Some(Action::ChangeMode(Module::CronEnv)));
+                }
                 Action::MoveUp => {
-                    self.previous_row();
+                    self.selection.previous();
                 }
                 Action::MoveDown => {
-                    self.next_row();
+                    self.selection.next();
                 }
                 Action::MoveToTheFirst => {
-                    self.first_row();
+                    self.selection.first();
                 }
                 Action::MoveToTheLast => {
-                    self.last_row();
+                    self.selection.last();
                 }
                 _ => {}
             }
@@ -343,22 +818,46 @@ impl Component for Cron {
 
     fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
         if self.enabled {
-            let vertical = Layout::vertical([Constraint::Min(1), Constraint::Length(2)]);
+            let vertical = Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(2),
+            ]);
             let rects = vertical.split(area);
 
-            self.draw_table(frame, rects[0]);
-            self.draw_scrollbar(frame, rects[0]);
-            self.draw_footer(
-                frame,
-                rects[1],
+            self.draw_title(frame, rects[0]);
+            match self.timeline_span {
+                Some(span) => self.draw_timeline(frame, rects[1], span),
+                None => self.draw_table(frame, rects[1]),
+            }
+            let keybinds = if self.searching {
+                vec![
+                    ("<Enter>", "Keep filter"),
+                    ("<Esc>", "Clear search"),
+                    ("<↓↑>", "Move up and down"),
+                ]
+            } else {
                 vec![
                     ("<Esc>", "Quit"),
                     ("<Enter>", "Edit selected cron"),
                     ("<↓↑>", "Move up and down"),
                     ("<d>", "Delete selected cron"),
+                    ("<t>", "Enable/disable"),
+                    ("<r>", "Run now"),
+                    ("<h>", "History"),
+                    ("<b>", "Backups"),
                     ("<n>", "Add new cron"),
-                ],
-            )?;
+                    ("<c>", "Clone"),
+                    ("<e>", "Environment"),
+                    ("<s>", "Switch crontab"),
+                    ("</>", "Search"),
+                    ("<o>", "Sort"),
+                    ("<v>", "Timeline"),
+                    ("<x>", "Export"),
+                    ("<i>", "Import"),
+                ]
+            };
+            self.draw_footer(frame, rects[2], keybinds)?;
         }
         Ok(())
     }
@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::json;
+
+use super::utils::validate_cron_expression;
+use crate::config::AiSettings;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+const SYSTEM_PROMPT: &str = "You convert schedules described in plain language into crontab \
+notation. Answer with the cron expression only, five fields (minute hour day-of-month month \
+day-of-week) or a macro such as @daily, without any explanation.";
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<Choice>,
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    message: Message,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    content: String,
+}
+
+/// Asks the configured chat-completions endpoint to turn `question` into a cron expression.
+pub async fn ask_for_cron(
+    settings: &AiSettings,
+    question: &str,
+    allow_seconds: bool,
+) -> Result<String, String> {
+    let mut request = reqwest::Client::new()
+        .post(&settings.url)
+        .timeout(TIMEOUT)
+        .json(&json!({
+            "model": settings.model,
+            "temperature": 0,
+            "messages": [
+                { "role": "system", "content": SYSTEM_PROMPT },
+                { "role": "user", "content": question },
+            ],
+        }));
+    if !settings.api_key_env.is_empty() {
+        match std::env::var(&settings.api_key_env) {
+            Ok(key) => request = request.bearer_auth(key),
+            Err(_) => return Err(format!("{} is not set", settings.api_key_env)),
+        }
+    }
+
+    let response = request.send().await.map_err(|e| {
+        if e.is_timeout() {
+            "The model did not answer in time".to_string()
+        } else {
+            format!("Request failed: {}", e.without_url())
+        }
+    })?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("The model endpoint answered {}", status));
+    }
+    let response: ChatResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Unexpected answer: {}", e.without_url()))?;
+
+    let content = response
+        .choices
+        .first()
+        .map(|choice| choice.message.content.as_str())
+        .unwrap_or_default();
+    extract_cron(content, allow_seconds)
+        .ok_or_else(|| "The answer did not contain a valid cron expression".to_string())
+}
+
+/// Finds the first valid cron expression in a model's free-form answer.
+pub fn extract_cron(content: &str, allow_seconds: bool) -> Option<String> {
+    let cleaned = content.replace('`', "\n");
+    cleaned.lines().find_map(|line| {
+        let tokens: Vec<&str> = line
+            .split_whitespace()
+            .map(|token| token.trim_end_matches(['.', ',', '!', ':']))
+            .collect();
+        let macros = tokens.iter().filter(|token| token.starts_with('@'));
+        let windows = [6, 5]
+            .into_iter()
+            .filter(|size| *size == 5 || allow_seconds)
+            .flat_map(|size| tokens.windows(size).map(|window| window.join(" ")));
+        macros
+            .map(|token| token.to_string())
+            .chain(windows)
+            .find(|candidate| validate_cron_expression(candidate, allow_seconds).is_ok())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// Serves a single HTTP response and hands back the request it received.
+    async fn mock_server(status: &str, body: &str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!(
+            "http://{}/v1/chat/completions",
+            listener.local_addr().unwrap()
+        );
+        let response = format!(
+            "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 4096];
+            loop {
+                let read = socket.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some((headers, body)) = text.split_once("\r\n\r\n") {
+                    let length = headers
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length: "))
+                        .and_then(|length| length.trim().parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break;
+                    }
+                }
+            }
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request).into_owned()
+        });
+        (url, handle)
+    }
+
+    fn settings(url: String) -> AiSettings {
+        AiSettings {
+            url,
+            model: "test-model".to_string(),
+            api_key_env: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_extract_cron() {
+        assert_eq!(
+            extract_cron("*/15 * * * *", false),
+            Some("*/15 * * * *".to_string())
+        );
+        assert_eq!(
+            extract_cron("Sure! Use `30 9 * * 1-5` to run on weekdays.", false),
+            Some("30 9 * * 1-5".to_string())
+        );
+        assert_eq!(
+            extract_cron("```\n0 0 * * 0\n```", false),
+            Some("0 0 * * 0".to_string())
+        );
+        assert_eq!(
+            extract_cron("That would be @daily.", false),
+            Some("@daily".to_string())
+        );
+        assert_eq!(extract_cron("I can't help with that", false), None);
+        assert_eq!(
+            extract_cron("0 */5 * * * *", true),
+            Some("0 */5 * * * *".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ask_for_cron() {
+        let (url, server) = mock_server(
+            "200 OK",
+            r#"{"choices":[{"message":{"role":"assistant","content":"`0 12 * * 1`"}}]}"#,
+        )
+        .await;
+        let answer = ask_for_cron(&settings(url), "mondays at noon", false).await;
+        assert_eq!(answer, Ok("0 12 * * 1".to_string()));
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /v1/chat/completions"));
+        assert!(request.contains("\"model\":\"test-model\""));
+        assert!(request.contains("mondays at noon"));
+    }
+
+    #[tokio::test]
+    async fn test_ask_for_cron_errors() {
+        let (url, _server) = mock_server("500 Internal Server Error", "{}").await;
+        assert_eq!(
+            ask_for_cron(&settings(url), "mondays", false).await,
+            Err("The model endpoint answered 500 Internal Server Error".to_string())
+        );
+
+        let (url, _server) = mock_server(
+            "200 OK",
+            r#"{"choices":[{"message":{"content":"No idea"}}]}"#,
+        )
+        .await;
+        assert_eq!(
+            ask_for_cron(&settings(url), "mondays", false).await,
+            Err("The answer did not contain a valid cron expression".to_string())
+        );
+
+        let mut settings = settings("http://127.0.0.1:9/".to_string());
+        settings.api_key_env = "TUIXEL_TEST_MISSING_KEY".to_string();
+        assert_eq!(
+            ask_for_cron(&settings, "mondays", false).await,
+            Err("TUIXEL_TEST_MISSING_KEY is not set".to_string())
+        );
+    }
+}
@@ -0,0 +1,313 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::source::write_atomically;
+
+pub const ANACRONTAB: &str = "/etc/anacrontab";
+
+/// Names anacron accepts instead of a number of days.
+const PERIOD_NAMES: [&str; 4] = ["@daily", "@weekly", "@monthly", "@yearly"];
+
+/// A job of an anacrontab.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnacronJob {
+    /// Days between runs, or one of `@daily`, `@weekly`, `@monthly` and `@yearly`.
+    pub period: String,
+    /// Minutes anacron waits after starting before it runs the job.
+    pub delay: String,
+    /// Names the job in anacron's messages and its timestamp file.
+    pub identifier: String,
+    pub command: String,
+    raw: Option<String>,
+}
+
+impl AnacronJob {
+    pub fn new(period: &str, delay: &str, identifier: &str, command: &str) -> Self {
+        Self {
+            period: period.trim().to_string(),
+            delay: delay.trim().to_string(),
+            identifier: identifier.trim().to_string(),
+            command: command.trim().to_string(),
+            raw: None,
+        }
+    }
+
+    /// Checks the fields the way anacron reads them.
+    pub fn validate(&self) -> Result<(), String> {
+        let is_number =
+            |value: &str| !value.is_empty() && value.chars().all(|c| c.is_ascii_digit());
+        if !is_number(&self.period) && !PERIOD_NAMES.contains(&self.period.as_str()) {
+            return Err(format!(
+                "The period is a number of days or one of {}",
+                PERIOD_NAMES.join(", ")
+            ));
+        }
+        if !is_number(&self.delay) {
+            return Err("The delay is a number of minutes".to_string());
+        }
+        if self.identifier.is_empty() || self.identifier.contains(['/', ' ', '\t']) {
+            return Err("The identifier is a single word without slashes".to_string());
+        }
+        if self.command.is_empty() {
+            return Err("The command is missing".to_string());
+        }
+        Ok(())
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            return None;
+        }
+        let (period, rest) = split_word(trimmed)?;
+        // Environment assignments, e.g. START_HOURS_RANGE=3-22.
+        if period.contains('=') {
+            return None;
+        }
+        let (delay, rest) = split_word(rest)?;
+        let (identifier, command) = split_word(rest)?;
+        if command.is_empty() {
+            return None;
+        }
+        Some(Self {
+            raw: Some(line.to_string()),
+            ..Self::new(period, delay, identifier, command)
+        })
+    }
+
+    fn render(&self) -> String {
+        match &self.raw {
+            Some(raw) if AnacronJob::parse(raw).is_some_and(|job| job.same_as(self)) => raw.clone(),
+            _ => format!(
+                "{}\t{}\t{}\t{}",
+                self.period, self.delay, self.identifier, self.command
+            ),
+        }
+    }
+
+    fn same_as(&self, other: &AnacronJob) -> bool {
+        self.period == other.period
+            && self.delay == other.delay
+            && self.identifier == other.identifier
+            && self.command == other.command
+    }
+}
+
+/// Splits the first word off `text`, returning it and the rest without leading whitespace.
+fn split_word(text: &str) -> Option<(&str, &str)> {
+    let text = text.trim_start();
+    if text.is_empty() {
+        return None;
+    }
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    Some((&text[..end], text[end..].trim_start()))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum AnacronLine {
+    Job(AnacronJob),
+    /// Comments, blank lines and environment assignments, kept as they are.
+    Other(String),
+}
+
+/// An anacrontab kept line by line, so that a save only touches the jobs that changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Anacrontab {
+    lines: Vec<AnacronLine>,
+}
+
+impl Anacrontab {
+    pub fn parse(content: &str) -> Self {
+        Self {
+            lines: content
+                .lines()
+                .map(|line| match AnacronJob::parse(line) {
+                    Some(job) => AnacronLine::Job(job),
+                    None => AnacronLine::Other(line.to_string()),
+                })
+                .collect(),
+        }
+    }
+
+    /// Reads the file, which is empty when it does not exist yet.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(Self::parse(&content)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_atomically(path, &self.render())
+    }
+
+    pub fn render(&self) -> String {
+        let mut content = String::new();
+        for line in &self.lines {
+            match line {
+                AnacronLine::Job(job) => content.push_str(&job.render()),
+                AnacronLine::Other(raw) => content.push_str(raw),
+            }
+            content.push('\n');
+        }
+        content
+    }
+
+    pub fn jobs(&self) -> impl Iterator<Item = &AnacronJob> {
+        self.lines.iter().filter_map(|line| match line {
+            AnacronLine::Job(job) => Some(job),
+            AnacronLine::Other(_) => None,
+        })
+    }
+
+    pub fn job(&self, index: usize) -> Option<&AnacronJob> {
+        self.jobs().nth(index)
+    }
+
+    /// Number of environment assignments.
+    pub fn env_count(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| match line {
+                AnacronLine::Other(raw) => split_word(raw).is_some_and(|(word, _)| {
+                    word.contains('=') && !raw.trim_start().starts_with('#')
+                }),
+                AnacronLine::Job(_) => false,
+            })
+            .count()
+    }
+
+    /// Replaces the job at `index`, or adds `job` at the end when `index` is `None`.
+    ///
+    /// Fails when the job is invalid or another job already has its identifier, since anacron
+    /// keeps track of when a job last ran by its identifier.
+    pub fn set_job(&mut self, index: Option<usize>, job: AnacronJob) -> Result<(), String> {
+        job.validate()?;
+        let taken = self
+            .jobs()
+            .enumerate()
+            .any(|(i, other)| Some(i) != index && other.identifier == job.identifier);
+        if taken {
+            return Err(format!("Another job is already called {}", job.identifier));
+        }
+
+        match index.and_then(|index| self.job_position(index)) {
+            Some(position) => {
+                if let AnacronLine::Job(current) = &mut self.lines[position] {
+                    current.period = job.period;
+                    current.delay = job.delay;
+                    current.identifier = job.identifier;
+                    current.command = job.command;
+                }
+            }
+            None => self.lines.push(AnacronLine::Job(job)),
+        }
+        Ok(())
+    }
+
+    pub fn remove_job(&mut self, index: usize) -> Option<AnacronJob> {
+        let position = self.job_position(index)?;
+        match self.lines.remove(position) {
+            AnacronLine::Job(job) => Some(job),
+            AnacronLine::Other(_) => None,
+        }
+    }
+
+    fn job_position(&self, index: usize) -> Option<usize> {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| matches!(line, AnacronLine::Job(_)))
+            .map(|(position, _)| position)
+            .nth(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const CONTENT: &str = "\
+# /etc/anacrontab: configuration file for anacron
+SHELL=/bin/sh
+START_HOURS_RANGE=3-22
+
+#period in days   delay in minutes   job-identifier   command
+1\t5\tcron.daily\trun-parts --report /etc/cron.daily
+7  25  cron.weekly   run-parts --report /etc/cron.weekly
+@monthly\t45\tcron.monthly\trun-parts --report /etc/cron.monthly
+";
+
+    #[test]
+    fn test_parse_and_render() {
+        let anacrontab = Anacrontab::parse(CONTENT);
+        let jobs: Vec<&AnacronJob> = anacrontab.jobs().collect();
+        assert_eq!(jobs.len(), 3);
+        assert_eq!(
+            jobs[1],
+            &AnacronJob {
+                raw: Some("7  25  cron.weekly   run-parts --report /etc/cron.weekly".to_string()),
+                ..AnacronJob::new(
+                    "7",
+                    "25",
+                    "cron.weekly",
+                    "run-parts --report /etc/cron.weekly"
+                )
+            }
+        );
+        assert_eq!(jobs[2].period, "@monthly");
+        assert_eq!(anacrontab.env_count(), 2);
+        assert_eq!(anacrontab.render(), CONTENT);
+    }
+
+    #[test]
+    fn test_edit() {
+        let mut anacrontab = Anacrontab::parse(CONTENT);
+        anacrontab
+            .set_job(
+                Some(1),
+                AnacronJob::new("14", "30", "cron.weekly", "/usr/local/bin/weekly"),
+            )
+            .unwrap();
+        anacrontab.remove_job(0);
+        anacrontab
+            .set_job(
+                None,
+                AnacronJob::new("@yearly", "60", "backup.yearly", "/usr/bin/backup"),
+            )
+            .unwrap();
+        assert_eq!(
+            anacrontab.render(),
+            "\
+# /etc/anacrontab: configuration file for anacron
+SHELL=/bin/sh
+START_HOURS_RANGE=3-22
+
+#period in days   delay in minutes   job-identifier   command
+14\t30\tcron.weekly\t/usr/local/bin/weekly
+@monthly\t45\tcron.monthly\trun-parts --report /etc/cron.monthly
+@yearly\t60\tbackup.yearly\t/usr/bin/backup
+"
+        );
+
+        assert_eq!(
+            anacrontab.set_job(None, AnacronJob::new("1", "5", "cron.monthly", "true")),
+            Err("Another job is already called cron.monthly".to_string())
+        );
+        assert!(anacrontab
+            .set_job(Some(1), AnacronJob::new("1", "5", "cron.monthly", "true"))
+            .is_ok());
+        assert!(AnacronJob::new("@hourly", "5", "x", "true")
+            .validate()
+            .is_err());
+        assert!(AnacronJob::new("1", "soon", "x", "true")
+            .validate()
+            .is_err());
+        assert!(AnacronJob::new("1", "5", "a/b", "true").validate().is_err());
+        assert!(AnacronJob::new("1", "5", "x", "").validate().is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use std::io;
+
+use crate::command::{run, run_with_output};
+
+/// A pending one-off job, as `atq` lists it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AtJob {
+    pub id: String,
+    /// When the job runs, e.g. "Sat Mar 1 10:00:00 2025".
+    pub time: String,
+    /// A letter from `a` to `z`, or `=` while the job runs.
+    pub queue: String,
+    pub user: String,
+}
+
+impl AtJob {
+    pub fn is_running(&self) -> bool {
+        self.queue == "="
+    }
+}
+
+/// Parses `atq`, which prints one `ID<tab>TIME QUEUE USER` line per job.
+pub fn parse_atq(output: &str) -> Vec<AtJob> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            if words.len() < 4 || !words[0].chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            let (time, owner) = words[1..].split_at(words.len() - 3);
+            Some(AtJob {
+                id: words[0].to_string(),
+                time: time.join(" "),
+                queue: owner[0].to_string(),
+                user: owner[1].to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The commands of a job, from the script `at -c` prints.
+///
+/// The script first restores the environment and working directory the job was scheduled
+/// from. Recent versions of `at` then pass the commands to the shell in a here-document;
+/// older ones simply list them after the `cd`.
+pub fn script_commands(script: &str) -> String {
+    let lines: Vec<&str> = script.lines().collect();
+    let here_document = lines.iter().enumerate().find_map(|(i, line)| {
+        let (_, delimiter) = line.split_once("<< '")?;
+        Some((i, delimiter.strip_suffix('\'')?))
+    });
+    let commands: Vec<&str> = match here_document {
+        Some((start, delimiter)) => lines[start + 1..]
+            .iter()
+            .take_while(|line| *line != &delimiter)
+            .copied()
+            .collect(),
+        None => {
+            let cd = lines
+                .iter()
+                .position(|line| line.starts_with("cd ") && line.ends_with("|| {"));
+            let start = cd
+                .and_then(|cd| {
+                    lines[cd..]
+                        .iter()
+                        .position(|line| *line == "}")
+                        .map(|end| cd + end + 1)
+                })
+                .unwrap_or(0);
+            lines[start..].to_vec()
+        }
+    };
+    commands.join("\n").trim().to_string()
+}
+
+pub fn list() -> io::Result<Vec<AtJob>> {
+    Ok(parse_atq(&run("atq", &[], None)?))
+}
+
+/// The script `at` runs for the job.
+pub fn script(id: &str) -> io::Result<String> {
+    run("at", &["-c", id], None)
+}
+
+pub fn remove(id: &str) -> io::Result<()> {
+    run("atrm", &[id], None).map(|_| ())
+}
+
+/// Schedules `command` for `time`, given in any form `at` understands ("now + 1 hour",
+/// "10:00 tomorrow", ...), and returns what `at` said about the new job.
+pub fn schedule(time: &str, command: &str) -> io::Result<String> {
+    let args = schedule_args(time).map_err(io::Error::other)?;
+    let output = run_with_output("at", &args, Some(command))?;
+    // `at` reports the job on stderr, after a warning about the shell it uses.
+    Ok(String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .find(|line| line.starts_with("job "))
+        .unwrap_or("Job scheduled")
+        .to_string())
+}
+
+/// The words of `time` as arguments of `at`, which must not take any of them for an option.
+pub fn schedule_args(time: &str) -> Result<Vec<&str>, String> {
+    let args: Vec<&str> = time.split_whitespace().collect();
+    match args.iter().find(|word| word.starts_with('-')) {
+        Some(word) => Err(format!(
+            "{} is not a time; options can't be given here",
+            word
+        )),
+        None => Ok(args),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/at")
+            .join(name)
+    }
+
+    #[test]
+    fn test_parse_atq() {
+        let output = fs::read_to_string(fixture("atq")).unwrap();
+        let jobs = parse_atq(&output);
+        assert_eq!(
+            jobs[0],
+            AtJob {
+                id: "12".to_string(),
+                time: "Sat Mar 1 10:00:00 2025".to_string(),
+                queue: "a".to_string(),
+                user: "deploy".to_string(),
+            }
+        );
+        assert_eq!(jobs[1].id, "13");
+        assert_eq!(jobs[1].time, "Mon Mar 10 03:30:00 2025");
+        assert!(jobs[1].is_running());
+        assert_eq!(jobs[1].user, "root");
+        assert_eq!(jobs.len(), 2);
+    }
+
+    #[test]
+    fn test_script_commands() {
+        let script = fs::read_to_string(fixture("at-c")).unwrap();
+        assert_eq!(
+            script_commands(&script),
+            "cd /srv/app && ./deploy.sh --finish\necho done"
+        );
+
+        let old = "\
+#!/bin/sh
+# atrun uid=0 gid=0
+umask 22
+HOME=/root; export HOME
+cd /root || {
+\t echo 'Execution directory inaccessible' >&2
+\t exit 1
+}
+/usr/sbin/reboot
+";
+        assert_eq!(script_commands(old), "/usr/sbin/reboot");
+    }
+
+    #[test]
+    fn test_schedule_args() {
+        assert_eq!(
+            schedule_args(" now + 1 hour "),
+            Ok(vec!["now", "+", "1", "hour"])
+        );
+        assert_eq!(
+            schedule_args("-f /etc/shadow now"),
+            Err("-f is not a time; options can't be given here".to_string())
+        );
+        assert!(schedule_args("10:00 -q b").is_err());
+    }
+}
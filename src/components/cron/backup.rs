@@ -0,0 +1,220 @@
+use chrono::{Local, NaiveDateTime};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use super::source::CrontabSource;
+use crate::config::get_data_dir;
+
+const PREFIX: &str = "crontab-";
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S%.3f";
+
+/// A saved copy of a crontab's earlier content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+    pub path: PathBuf,
+    pub created: NaiveDateTime,
+}
+
+impl Backup {
+    pub fn read(&self) -> io::Result<String> {
+        fs::read_to_string(&self.path)
+    }
+}
+
+/// Timestamped copies of one crontab, kept in their own directory.
+#[derive(Debug, Clone)]
+pub struct Backups {
+    dir: PathBuf,
+    retention: usize,
+}
+
+impl Backups {
+    pub fn new(dir: PathBuf, retention: usize) -> Self {
+        Self { dir, retention }
+    }
+
+    /// Backups of `source`, stored under the tuixel data dir.
+    pub fn for_source(source: &CrontabSource, retention: usize) -> Self {
+        let name = match source {
+            CrontabSource::CurrentUser => "current-user".to_string(),
+            CrontabSource::User(user) => format!("user-{}", user),
+            CrontabSource::System(path) => {
+                format!("system{}", path.to_string_lossy().replace(['/', '\\'], "_"))
+            }
+        };
+        Self::new(get_data_dir().join("backups").join(name), retention)
+    }
+
+    /// Saves `content` as the newest backup and drops the ones beyond the retention count.
+    /// Empty content is not worth a backup.
+    ///
+    /// Crontabs can hold secrets, so only their owner may read the backups.
+    pub fn create(&self, content: &str) -> io::Result<Option<PathBuf>> {
+        if self.retention == 0 || content.trim().is_empty() {
+            return Ok(None);
+        }
+        let mut dir = fs::DirBuilder::new();
+        dir.recursive(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+            dir.mode(0o700).create(&self.dir)?;
+            // Directories made before are tightened too.
+            fs::set_permissions(&self.dir, fs::Permissions::from_mode(0o700))?;
+        }
+        #[cfg(not(unix))]
+        dir.create(&self.dir)?;
+
+        // Keep backups ordered even when several are made within the same millisecond.
+        let mut created = Local::now().naive_local();
+        if let Some(newest) = self.list()?.first() {
+            created = created.max(newest.created + chrono::Duration::milliseconds(1));
+        }
+        let path = self.path_for(&created);
+        let mut file = fs::OpenOptions::new();
+        file.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            file.mode(0o600);
+        }
+        file.open(&path)?.write_all(content.as_bytes())?;
+
+        for backup in self.list()?.into_iter().skip(self.retention) {
+            fs::remove_file(backup.path)?;
+        }
+        Ok(Some(path))
+    }
+
+    /// Lists the backups, newest first.
+    pub fn list(&self) -> io::Result<Vec<Backup>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut backups: Vec<Backup> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let created =
+                    NaiveDateTime::parse_from_str(name.strip_prefix(PREFIX)?, TIMESTAMP_FORMAT)
+                        .ok()?;
+                Some(Backup {
+                    path: entry.path(),
+                    created,
+                })
+            })
+            .collect();
+        backups.sort_by_key(|backup| std::cmp::Reverse(backup.created));
+        Ok(backups)
+    }
+
+    fn path_for(&self, time: &NaiveDateTime) -> PathBuf {
+        self.dir
+            .join(format!("{}{}", PREFIX, time.format(TIMESTAMP_FORMAT)))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::components::cron::{
+        crontab::Crontab,
+        utils::{save_to_crontab, SaveError},
+    };
+    use crate::config::CronSettings;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tuixel-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_create_and_prune() {
+        let dir = temp_dir("backups");
+        let backups = Backups::new(dir.clone(), 3);
+
+        assert_eq!(backups.create("  \n").unwrap(), None);
+        for i in 0..5 {
+            backups
+                .create(&format!("0 {} * * * /usr/bin/job\n", i))
+                .unwrap();
+        }
+
+        let list = backups.list().unwrap();
+        assert_eq!(list.len(), 3);
+        assert!(list
+            .windows(2)
+            .all(|pair| pair[0].created > pair[1].created));
+        assert_eq!(list[0].read().unwrap(), "0 4 * * * /usr/bin/job\n");
+        assert_eq!(list[2].read().unwrap(), "0 2 * * * /usr/bin/job\n");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(&dir), 0o700);
+            assert!(list.iter().all(|backup| mode(&backup.path) == 0o600));
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_restore_backs_up_current_content() {
+        let dir = temp_dir("restore");
+        fs::create_dir_all(&dir).unwrap();
+        let crontab = dir.join("crontab");
+        fs::write(&crontab, "0 1 * * * root /usr/bin/old\n").unwrap();
+        let source = CrontabSource::System(crontab.clone());
+        let backups = Backups::new(dir.join("backups"), 10);
+
+        backups.create("0 1 * * * root /usr/bin/old\n").unwrap();
+        fs::write(&crontab, "0 1 * * * root /usr/bin/new\n").unwrap();
+        let oldest = backups.list().unwrap().remove(0);
+
+        // A restore is saved as any other change, which refuses to overwrite changes made
+        // since the crontab was read.
+        let settings = CronSettings::default();
+        let mut table = Crontab::parse("0 1 * * * root /usr/bin/stale\n", true, &settings);
+        table.replace(&oldest.read().unwrap(), &settings);
+        assert!(matches!(
+            save_to_crontab(&source, &mut table, &backups),
+            Err(SaveError::Conflict(_))
+        ));
+
+        let mut table = Crontab::parse(&fs::read_to_string(&crontab).unwrap(), true, &settings);
+        table.replace(&oldest.read().unwrap(), &settings);
+        save_to_crontab(&source, &mut table, &backups).unwrap();
+        assert!(!table.is_modified());
+
+        assert_eq!(
+            fs::read_to_string(&crontab).unwrap(),
+            "0 1 * * * root /usr/bin/old\n"
+        );
+        assert_eq!(
+            backups.list().unwrap()[0].read().unwrap(),
+            "0 1 * * * root /usr/bin/new\n"
+        );
+        assert_eq!(backups.list().unwrap().len(), 2);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_disabled_backups() {
+        let dir = temp_dir("disabled");
+        let backups = Backups::new(dir.clone(), 0);
+        assert_eq!(backups.create("0 1 * * * /usr/bin/job\n").unwrap(), None);
+        assert!(!dir.exists());
+    }
+}
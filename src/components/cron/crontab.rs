@@ -0,0 +1,743 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::components::cron::{
+    utils::{get_job_next_execution, is_day_of_week_field},
+    wrapper::Wrapper,
+    CronJob,
+};
+use crate::config::CronSettings;
+
+/// Prefix tuixel puts in front of the jobs it disables.
+pub const DISABLED_MARKER: &str = "#DISABLED#";
+
+/// Sets the timezone of the jobs that follow it (cronie).
+pub const CRON_TZ: &str = "CRON_TZ";
+
+/// A crontab kept line by line, so that comments, blank lines, environment assignments and
+/// anything tuixel does not understand survive a save untouched.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Crontab {
+    system: bool,
+    seconds: bool,
+    /// The zone jobs run in without a `CRON_TZ` line.
+    timezone: String,
+    lines: Vec<CrontabLine>,
+    saved_content: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CrontabLine {
+    Blank(String),
+    Comment(String),
+    Env(EnvVar),
+    Job(CronJob),
+    Unknown(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EnvVar {
+    pub name: String,
+    pub value: String,
+    raw: Option<String>,
+}
+
+impl EnvVar {
+    pub fn new(name: &str, value: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.to_string(),
+            raw: None,
+        }
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let (name, value) = line.split_once('=')?;
+        let name = name.trim();
+        let mut chars = name.chars();
+        let first = chars.next()?;
+        if !(first.is_ascii_alphabetic() || first == '_')
+            || !chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return None;
+        }
+        Some(Self {
+            name: name.to_string(),
+            value: value.trim().to_string(),
+            raw: Some(line.to_string()),
+        })
+    }
+
+    fn render(&self) -> String {
+        match &self.raw {
+            Some(raw) if EnvVar::parse(raw).is_some_and(|var| var.same_as(self)) => raw.clone(),
+            _ => format!("{}={}", self.name, self.value),
+        }
+    }
+
+    fn same_as(&self, other: &EnvVar) -> bool {
+        self.name == other.name && self.value == other.value
+    }
+}
+
+impl Crontab {
+    pub fn parse(content: &str, system: bool, settings: &CronSettings) -> Self {
+        let mut lines: Vec<CrontabLine> = Vec::new();
+
+        for raw in content.lines() {
+            let line = raw.trim();
+
+            if line.is_empty() {
+                lines.push(CrontabLine::Blank(raw.to_string()));
+            } else if let Some(var) = EnvVar::parse(raw) {
+                lines.push(CrontabLine::Env(var));
+            } else if let Some(mut job) = parse_job_line(raw, system, settings.seconds) {
+                // A comment directly above a job is its description.
+                if let Some(CrontabLine::Comment(comment)) = lines.last() {
+                    job.job_description = comment_text(comment);
+                    job.raw_description = Some(comment.clone());
+                    lines.pop();
+                }
+                lines.push(CrontabLine::Job(job));
+            } else if line.starts_with('#') {
+                lines.push(CrontabLine::Comment(raw.to_string()));
+            } else {
+                lines.push(CrontabLine::Unknown(raw.to_string()));
+            }
+        }
+
+        let mut crontab = Self {
+            system,
+            seconds: settings.seconds,
+            timezone: settings.timezone.clone(),
+            lines,
+            saved_content: content.to_string(),
+        };
+        crontab.update_schedule();
+        crontab
+    }
+
+    /// Works out every job's `CRON_TZ` and next execution again, after jobs or environment
+    /// assignments changed.
+    pub fn update_schedule(&mut self) {
+        let mut zone = String::new();
+        for line in &mut self.lines {
+            match line {
+                CrontabLine::Env(var) if var.name == CRON_TZ => zone = var.value.clone(),
+                CrontabLine::Job(job) => {
+                    job.timezone = zone.clone();
+                    job.next_execution = if job.disabled {
+                        "Disabled".to_string()
+                    } else {
+                        get_job_next_execution(&job.cron_notation, &zone, &self.timezone)
+                    };
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Makes the job at `index` run in `zone`, or in the default zone when `zone` is empty.
+    ///
+    /// The `CRON_TZ` line right above the job is updated, removed or added as needed, and a
+    /// line restoring the previous zone is added below the job when other jobs follow it.
+    pub fn set_job_timezone(&mut self, index: usize, zone: &str) {
+        let Some(mut position) = self.job_position(index) else {
+            return;
+        };
+        let wanted = if zone.is_empty() {
+            self.default_zone()
+        } else {
+            zone.to_string()
+        };
+        let current = self.zone_at(position);
+        if current == wanted {
+            return;
+        }
+
+        let above = position
+            .checked_sub(1)
+            .filter(|&above| is_cron_tz(&self.lines[above]));
+        match above {
+            Some(above) if self.zone_at(above) == wanted => {
+                self.lines.remove(above);
+                position -= 1;
+            }
+            Some(above) => self.lines[above] = CrontabLine::Env(EnvVar::new(CRON_TZ, &wanted)),
+            None => {
+                self.lines
+                    .insert(position, CrontabLine::Env(EnvVar::new(CRON_TZ, &wanted)));
+                position += 1;
+            }
+        }
+
+        let next_job_runs_here = self.lines[position + 1..]
+            .iter()
+            .find(|line| matches!(line, CrontabLine::Job(_)) || is_cron_tz(line))
+            .is_some_and(|line| matches!(line, CrontabLine::Job(_)));
+        if next_job_runs_here && self.zone_at(position + 1) != current {
+            self.lines.insert(
+                position + 1,
+                CrontabLine::Env(EnvVar::new(CRON_TZ, &current)),
+            );
+        }
+        self.update_schedule();
+    }
+
+    /// Removes the `CRON_TZ` lines no job runs under any more, e.g. once the jobs below one
+    /// were removed, so that they don't change the zone of jobs added later.
+    pub fn prune_zones(&mut self) {
+        let mut job_below = false;
+        for position in (0..self.lines.len()).rev() {
+            match &self.lines[position] {
+                CrontabLine::Job(_) => job_below = true,
+                line if is_cron_tz(line) => {
+                    if !job_below {
+                        self.lines.remove(position);
+                    }
+                    job_below = false;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The zone in effect for a line at `position`.
+    fn zone_at(&self, position: usize) -> String {
+        self.lines[..position]
+            .iter()
+            .rev()
+            .find_map(|line| match line {
+                CrontabLine::Env(var) if var.name == CRON_TZ && !var.value.is_empty() => {
+                    Some(var.value.clone())
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| self.default_zone())
+    }
+
+    /// The zone jobs run in without a `CRON_TZ` line.
+    pub fn default_zone(&self) -> String {
+        if self.timezone.is_empty() {
+            "UTC".to_string()
+        } else {
+            self.timezone.clone()
+        }
+    }
+
+    /// Renders the crontab, reusing the original text of every line that was not changed.
+    pub fn render(&self) -> String {
+        let mut content = String::new();
+
+        for line in &self.lines {
+            match line {
+                CrontabLine::Blank(raw) | CrontabLine::Comment(raw) | CrontabLine::Unknown(raw) => {
+                    content.push_str(raw);
+                }
+                CrontabLine::Env(var) => content.push_str(&var.render()),
+                CrontabLine::Job(job) => {
+                    content.push_str(&render_job(job, self.system, self.seconds));
+                }
+            }
+            content.push('\n');
+        }
+
+        content
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.render() != self.saved_content
+    }
+
+    /// The content as it was last read from or written to its source.
+    pub fn saved_content(&self) -> &str {
+        &self.saved_content
+    }
+
+    /// Drops the changes made since the content was last read or written.
+    pub fn revert(&mut self, settings: &CronSettings) {
+        *self = Self::parse(&self.saved_content, self.system, settings);
+    }
+
+    /// Replaces the content with `content`, which then counts as a change to be saved.
+    pub fn replace(&mut self, content: &str, settings: &CronSettings) {
+        let saved = std::mem::take(&mut self.saved_content);
+        *self = Self::parse(content, self.system, settings);
+        self.saved_content = saved;
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.saved_content = self.render();
+    }
+
+    /// Fingerprint of the content as it was last read from or written to its source.
+    pub fn fingerprint(&self) -> u64 {
+        fingerprint(&self.saved_content)
+    }
+
+    pub fn jobs(&self) -> impl Iterator<Item = &CronJob> {
+        self.lines.iter().filter_map(|line| match line {
+            CrontabLine::Job(job) => Some(job),
+            _ => None,
+        })
+    }
+
+    pub fn job_count(&self) -> usize {
+        self.jobs().count()
+    }
+
+    pub fn job(&self, index: usize) -> Option<&CronJob> {
+        self.jobs().nth(index)
+    }
+
+    pub fn job_mut(&mut self, index: usize) -> Option<&mut CronJob> {
+        self.lines
+            .iter_mut()
+            .filter_map(|line| match line {
+                CrontabLine::Job(job) => Some(job),
+                _ => None,
+            })
+            .nth(index)
+    }
+
+    pub fn push_job(&mut self, job: CronJob) {
+        if !matches!(self.lines.last(), None | Some(CrontabLine::Blank(_))) {
+            self.lines.push(CrontabLine::Blank(String::new()));
+        }
+        self.lines.push(CrontabLine::Job(job));
+    }
+
+    pub fn remove_job(&mut self, index: usize) -> Option<CronJob> {
+        let position = self.job_position(index)?;
+        match self.lines.remove(position) {
+            CrontabLine::Job(job) => Some(job),
+            _ => None,
+        }
+    }
+
+    fn job_position(&self, index: usize) -> Option<usize> {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| matches!(line, CrontabLine::Job(_)))
+            .map(|(position, _)| position)
+            .nth(index)
+    }
+
+    pub fn env_vars(&self) -> impl Iterator<Item = &EnvVar> {
+        self.lines.iter().filter_map(|line| match line {
+            CrontabLine::Env(var) => Some(var),
+            _ => None,
+        })
+    }
+
+    /// Replaces the environment assignments while keeping them where they are in the file.
+    ///
+    /// The n-th assignment of a name updates the n-th existing line for that name, assignments
+    /// that are gone are removed and new ones are added after the last existing assignment
+    /// other than a job's `CRON_TZ` (or at the top of the file).
+    pub fn set_env_vars(&mut self, vars: &[(String, String)]) {
+        let mut remaining: Vec<Option<&(String, String)>> = vars.iter().map(Some).collect();
+
+        self.lines.retain_mut(|line| {
+            let CrontabLine::Env(var) = line else {
+                return true;
+            };
+            let matching = remaining
+                .iter_mut()
+                .find(|entry| entry.is_some_and(|(name, _)| *name == var.name));
+            match matching {
+                Some(entry) => {
+                    var.value = entry.take().unwrap().1.clone();
+                    true
+                }
+                None => false,
+            }
+        });
+
+        let mut insert_at = self
+            .lines
+            .iter()
+            .rposition(|line| matches!(line, CrontabLine::Env(_)) && !is_cron_tz(line))
+            .map(|position| position + 1)
+            .unwrap_or(0);
+        let mut added = false;
+        for (name, value) in remaining.into_iter().flatten() {
+            self.lines
+                .insert(insert_at, CrontabLine::Env(EnvVar::new(name, value)));
+            insert_at += 1;
+            added = true;
+        }
+        if added
+            && insert_at < self.lines.len()
+            && !matches!(
+                self.lines[insert_at],
+                CrontabLine::Blank(_) | CrontabLine::Env(_)
+            )
+        {
+            self.lines
+                .insert(insert_at, CrontabLine::Blank(String::new()));
+        }
+        self.update_schedule();
+    }
+}
+
+fn is_cron_tz(line: &CrontabLine) -> bool {
+    matches!(line, CrontabLine::Env(var) if var.name == CRON_TZ)
+}
+
+/// Parses a job line, including jobs disabled with [`DISABLED_MARKER`].
+fn parse_job_line(line: &str, system: bool, seconds: bool) -> Option<CronJob> {
+    let trimmed = line.trim_start();
+    let (disabled, definition) = match trimmed.strip_prefix(DISABLED_MARKER) {
+        Some(definition) => (true, definition),
+        None if trimmed.starts_with('#') => return None,
+        None => (false, trimmed),
+    };
+    let parts: Vec<&str> = definition.split_whitespace().collect();
+    let time_fields = if parts.first()?.starts_with('@') {
+        1
+    } else if seconds
+        && parts
+            .get(5)
+            .is_some_and(|field| is_day_of_week_field(field))
+    {
+        6
+    } else {
+        5
+    };
+    let min_fields = time_fields + if system { 2 } else { 1 };
+
+    if parts.len() < min_fields {
+        return None;
+    }
+
+    let cron_notation = parts[..time_fields].join(" ");
+    let (user, job) = if system {
+        (
+            parts[time_fields].to_string(),
+            parts[time_fields + 1..].join(" "),
+        )
+    } else {
+        (String::new(), parts[time_fields..].join(" "))
+    };
+
+    let (job, wrapper) = Wrapper::unwrap(&job);
+    Some(CronJob {
+        cron_notation,
+        job,
+        wrapper,
+        user,
+        disabled,
+        raw: Some(line.to_string()),
+        ..CronJob::default()
+    })
+}
+
+fn render_job(job: &CronJob, system: bool, seconds: bool) -> String {
+    let mut content = String::new();
+
+    if !job.job_description.is_empty() {
+        match &job.raw_description {
+            Some(raw) if comment_text(raw) == job.job_description => content.push_str(raw),
+            _ => content.push_str(&format!("# {}", job.job_description)),
+        }
+        content.push('\n');
+    }
+
+    let unchanged = job
+        .raw
+        .as_deref()
+        .and_then(|raw| parse_job_line(raw, system, seconds))
+        .is_some_and(|parsed| {
+            parsed.cron_notation == job.cron_notation
+                && parsed.job == job.job
+                && parsed.wrapper == job.wrapper
+                && parsed.user == job.user
+                && parsed.disabled == job.disabled
+        });
+
+    if let Some(raw) = job.raw.as_deref().filter(|_| unchanged) {
+        content.push_str(raw);
+        return content;
+    }
+
+    if job.disabled {
+        content.push_str(&format!("{} ", DISABLED_MARKER));
+    }
+    if system {
+        let user = if job.user.is_empty() {
+            "root"
+        } else {
+            &job.user
+        };
+        content.push_str(&format!("{} {} {}", job.cron_notation, user, job.command()));
+    } else {
+        content.push_str(&format!("{} {}", job.cron_notation, job.command()));
+    }
+
+    content
+}
+
+/// Hash used to tell whether a crontab changed on disk since it was loaded.
+pub fn fingerprint(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn comment_text(comment: &str) -> String {
+    comment.trim().trim_start_matches('#').trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn settings() -> CronSettings {
+        CronSettings {
+            timezone: "UTC".to_string(),
+            ..CronSettings::default()
+        }
+    }
+
+    const CONTENT: &str = "\
+# Edit this file to introduce tasks to be run by cron.
+#
+MAILTO=ops@example.com
+PATH=/usr/local/bin:/usr/bin:/bin
+
+# Nightly backup
+# (keeps 7 days)
+0   3 * * * /usr/bin/backup --all
+*/5 * * * *  /usr/local/bin/healthcheck >/dev/null 2>&1
+this line is not understood
+";
+
+    #[test]
+    fn test_round_trip_is_lossless() {
+        let crontab = Crontab::parse(CONTENT, false, &settings());
+        assert_eq!(crontab.render(), CONTENT);
+        assert!(!crontab.is_modified());
+    }
+
+    #[test]
+    fn test_jobs_and_descriptions() {
+        let crontab = Crontab::parse(CONTENT, false, &settings());
+        assert_eq!(crontab.job_count(), 2);
+        let backup = crontab.job(0).unwrap();
+        assert_eq!(backup.cron_notation, "0 3 * * *");
+        assert_eq!(backup.job, "/usr/bin/backup --all");
+        assert_eq!(backup.job_description, "(keeps 7 days)");
+        assert_eq!(crontab.job(1).unwrap().job_description, "");
+    }
+
+    #[test]
+    fn test_only_changed_lines_are_rewritten() {
+        let mut crontab = Crontab::parse(CONTENT, false, &settings());
+        crontab.job_mut(1).unwrap().cron_notation = "*/10 * * * *".to_string();
+
+        let rendered = crontab.render();
+        assert!(crontab.is_modified());
+        assert!(rendered.contains("0   3 * * * /usr/bin/backup --all\n"));
+        assert!(rendered.contains("\n*/10 * * * * /usr/local/bin/healthcheck >/dev/null 2>&1\n"));
+        assert!(rendered.contains("MAILTO=ops@example.com\n"));
+        assert!(rendered.contains("this line is not understood\n"));
+    }
+
+    #[test]
+    fn test_remove_job_keeps_other_lines() {
+        let mut crontab = Crontab::parse(CONTENT, false, &settings());
+        crontab.remove_job(0);
+
+        let rendered = crontab.render();
+        assert!(!rendered.contains("/usr/bin/backup"));
+        assert!(rendered.contains("# Nightly backup\n"));
+        assert!(rendered.contains("PATH=/usr/local/bin:/usr/bin:/bin\n"));
+    }
+
+    #[test]
+    fn test_cron_tz() {
+        let content = "\
+0 1 * * * /usr/bin/local
+CRON_TZ=America/New_York
+0 2 * * * /usr/bin/new-york
+0 3 * * * /usr/bin/also-new-york
+";
+        let mut crontab = Crontab::parse(content, false, &settings());
+        let zones: Vec<&str> = crontab.jobs().map(|job| job.timezone.as_str()).collect();
+        assert_eq!(zones, vec!["", "America/New_York", "America/New_York"]);
+        assert!(crontab.job(1).unwrap().next_execution.contains(" E"));
+        assert!(crontab.job(1).unwrap().next_execution.ends_with(" UTC)"));
+
+        // Unchanged zones leave the file alone.
+        crontab.set_job_timezone(1, "America/New_York");
+        crontab.set_job_timezone(0, "");
+        assert_eq!(crontab.render(), content);
+
+        crontab.set_job_timezone(0, "Asia/Tokyo");
+        crontab.set_job_timezone(2, "");
+        assert_eq!(
+            crontab.render(),
+            "\
+CRON_TZ=Asia/Tokyo
+0 1 * * * /usr/bin/local
+CRON_TZ=America/New_York
+0 2 * * * /usr/bin/new-york
+CRON_TZ=UTC
+0 3 * * * /usr/bin/also-new-york
+"
+        );
+        let zones: Vec<&str> = crontab.jobs().map(|job| job.timezone.as_str()).collect();
+        assert_eq!(zones, vec!["Asia/Tokyo", "America/New_York", "UTC"]);
+
+        crontab.set_job_timezone(1, "Asia/Tokyo");
+        assert_eq!(
+            crontab.render(),
+            "\
+CRON_TZ=Asia/Tokyo
+0 1 * * * /usr/bin/local
+0 2 * * * /usr/bin/new-york
+CRON_TZ=UTC
+0 3 * * * /usr/bin/also-new-york
+"
+        );
+    }
+
+    #[test]
+    fn test_revert() {
+        let mut crontab = Crontab::parse(CONTENT, false, &settings());
+        crontab.remove_job(0);
+        crontab.job_mut(0).unwrap().cron_notation = "@hourly".to_string();
+        assert!(crontab.is_modified());
+
+        crontab.revert(&settings());
+        assert!(!crontab.is_modified());
+        assert_eq!(crontab.render(), CONTENT);
+        assert_eq!(crontab.job_count(), 2);
+    }
+
+    #[test]
+    fn test_set_env_vars() {
+        let mut crontab = Crontab::parse(CONTENT, false, &settings());
+        crontab.set_env_vars(&[
+            ("MAILTO".to_string(), "root".to_string()),
+            ("SHELL".to_string(), "/bin/bash".to_string()),
+        ]);
+
+        let vars: Vec<(String, String)> = crontab
+            .env_vars()
+            .map(|var| (var.name.clone(), var.value.clone()))
+            .collect();
+        assert_eq!(
+            vars,
+            vec![
+                ("MAILTO".to_string(), "root".to_string()),
+                ("SHELL".to_string(), "/bin/bash".to_string()),
+            ]
+        );
+        assert!(crontab
+            .render()
+            .starts_with("# Edit this file to introduce tasks to be run by cron.\n#\nMAILTO=root\nSHELL=/bin/bash\n\n"));
+    }
+
+    #[test]
+    fn test_system_crontab() {
+        let content =
+            "SHELL=/bin/sh\n17 * * * * root cd / && run-parts --report /etc/cron.hourly\n";
+        let mut crontab = Crontab::parse(content, true, &settings());
+        assert_eq!(crontab.job(0).unwrap().user, "root");
+        assert_eq!(
+            crontab.job(0).unwrap().job,
+            "cd / && run-parts --report /etc/cron.hourly"
+        );
+
+        crontab.job_mut(0).unwrap().user = "www-data".to_string();
+        assert!(crontab
+            .render()
+            .contains("17 * * * * www-data cd / && run-parts --report /etc/cron.hourly\n"));
+    }
+
+    #[test]
+    fn test_macros_and_seconds() {
+        let content = "@reboot /usr/local/bin/warmup\n@daily root /usr/sbin/logrotate\n";
+        let crontab = Crontab::parse(content, false, &settings());
+        assert_eq!(crontab.job(0).unwrap().cron_notation, "@reboot");
+        assert_eq!(crontab.job(0).unwrap().job, "/usr/local/bin/warmup");
+        assert_eq!(crontab.job(0).unwrap().next_execution, "At boot");
+
+        let system = Crontab::parse(content, true, &settings());
+        assert_eq!(system.job_count(), 1);
+        assert_eq!(system.job(0).unwrap().user, "root");
+
+        let with_seconds = CronSettings {
+            seconds: true,
+            ..settings()
+        };
+        let crontab = Crontab::parse("30 */5 * * * * /usr/bin/poll\n", false, &with_seconds);
+        assert_eq!(crontab.job(0).unwrap().cron_notation, "30 */5 * * * *");
+        assert_eq!(crontab.job(0).unwrap().job, "/usr/bin/poll");
+    }
+
+    #[test]
+    fn test_mixed_field_counts_with_seconds() {
+        let with_seconds = CronSettings {
+            seconds: true,
+            ..settings()
+        };
+        let content = "0 4 * * * /usr/bin/backup\n30 */5 * * * 1-5 /usr/bin/poll\n";
+        let crontab = Crontab::parse(content, false, &with_seconds);
+        assert_eq!(crontab.job(0).unwrap().cron_notation, "0 4 * * *");
+        assert_eq!(crontab.job(0).unwrap().job, "/usr/bin/backup");
+        assert_eq!(crontab.job(1).unwrap().cron_notation, "30 */5 * * * 1-5");
+        assert_eq!(crontab.job(1).unwrap().job, "/usr/bin/poll");
+        assert_eq!(crontab.render(), content);
+
+        let system = Crontab::parse("0 4 * * * root /usr/bin/backup\n", true, &with_seconds);
+        assert_eq!(system.job(0).unwrap().user, "root");
+        assert_eq!(system.job(0).unwrap().job, "/usr/bin/backup");
+    }
+
+    #[test]
+    fn test_disabled_jobs() {
+        let content = "# Reindex\n#DISABLED# 0 2 * * * /usr/bin/reindex\n# just a note\n";
+        let mut crontab = Crontab::parse(content, false, &settings());
+        assert_eq!(crontab.job_count(), 1);
+        let job = crontab.job(0).unwrap();
+        assert!(job.disabled);
+        assert_eq!(job.job, "/usr/bin/reindex");
+        assert_eq!(job.job_description, "Reindex");
+        assert_eq!(job.next_execution, "Disabled");
+        assert_eq!(crontab.render(), content);
+
+        crontab.job_mut(0).unwrap().disabled = false;
+        assert_eq!(
+            crontab.render(),
+            "# Reindex\n0 2 * * * /usr/bin/reindex\n# just a note\n"
+        );
+        crontab.job_mut(0).unwrap().disabled = true;
+        assert_eq!(crontab.render(), content);
+    }
+
+    #[test]
+    fn test_wrapped_jobs() {
+        let content =
+            "0 3 * * * flock -n /tmp/backup.lock sh -c 'backup --all' >> /tmp/backup.log 2>&1\n";
+        let mut crontab = Crontab::parse(content, false, &settings());
+        let job = crontab.job(0).unwrap();
+        assert_eq!(job.job, "backup --all");
+        assert_eq!(job.wrapper.lock.as_deref(), Some("/tmp/backup.lock"));
+        assert_eq!(job.wrapper.log.as_deref(), Some("/tmp/backup.log"));
+        assert_eq!(crontab.render(), content);
+
+        let job = crontab.job_mut(0).unwrap();
+        job.wrapper.log = None;
+        job.wrapper.timeout = Some("1h".to_string());
+        assert_eq!(
+            crontab.render(),
+            "0 3 * * * flock -n /tmp/backup.lock timeout 1h sh -c 'backup --all'\n"
+        );
+    }
+}
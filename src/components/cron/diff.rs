@@ -0,0 +1,64 @@
+use ratatui::{
+    style::{Color, Style},
+    text::Line,
+};
+
+/// One line of a line-based diff between two versions of a crontab.
+#[derive(Debug, PartialEq)]
+pub enum DiffLine<'a> {
+    Same(&'a str),
+    Added(&'a str),
+    Removed(&'a str),
+}
+
+/// Diffs `old` against `new` line by line.
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    diff::lines(old, new)
+        .into_iter()
+        .map(|line| match line {
+            diff::Result::Both(line, _) => DiffLine::Same(line),
+            diff::Result::Left(line) => DiffLine::Removed(line),
+            diff::Result::Right(line) => DiffLine::Added(line),
+        })
+        .collect()
+}
+
+/// Renders a diff the way `diff -u` prints its lines, coloured.
+pub fn diff_to_lines(old: &str, new: &str) -> Vec<Line<'static>> {
+    diff_lines(old, new)
+        .into_iter()
+        .map(|line| match line {
+            DiffLine::Same(line) => Line::raw(format!("  {}", line)),
+            DiffLine::Added(line) => Line::styled(
+                format!("+ {}", line),
+                Style::default().fg(Color::LightGreen),
+            ),
+            DiffLine::Removed(line) => {
+                Line::styled(format!("- {}", line), Style::default().fg(Color::LightRed))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "MAILTO=ops\n0 3 * * * /usr/bin/backup\n";
+        let new = "MAILTO=ops\n0 4 * * * /usr/bin/backup\n*/5 * * * * /usr/bin/ping\n";
+        assert_eq!(
+            diff_lines(old, new),
+            vec![
+                DiffLine::Same("MAILTO=ops"),
+                DiffLine::Removed("0 3 * * * /usr/bin/backup"),
+                DiffLine::Added("0 4 * * * /usr/bin/backup"),
+                DiffLine::Added("*/5 * * * * /usr/bin/ping"),
+                DiffLine::Same(""),
+            ]
+        );
+    }
+}
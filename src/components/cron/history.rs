@@ -0,0 +1,296 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// Log files cron writes to, depending on the distribution's syslog setup.
+const LOG_FILES: [&str; 4] = [
+    "/var/log/cron",
+    "/var/log/cron.log",
+    "/var/log/syslog",
+    "/var/log/messages",
+];
+/// Systemd units cron runs as (`cron` on Debian, `crond` on Red Hat).
+const JOURNAL_UNITS: [&str; 2] = ["cron", "crond"];
+
+/// How many past events the history pane keeps.
+pub const HISTORY_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HistoryKind {
+    /// Cron started the job.
+    Run,
+    /// Cron mailed (or tried to mail) the job's output.
+    Mail,
+    /// Cron reported a failure for the job.
+    Error,
+}
+
+/// One cron log event for a job.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: String,
+    pub user: String,
+    pub kind: HistoryKind,
+    pub message: String,
+}
+
+/// A cron line of a syslog-style log, e.g.
+/// `Oct 17 03:00:01 host CRON[1234]: (root) CMD (/usr/bin/backup)`.
+#[derive(Debug, PartialEq)]
+struct LogLine<'a> {
+    timestamp: String,
+    pid: &'a str,
+    user: &'a str,
+    keyword: &'a str,
+    message: &'a str,
+}
+
+fn parse_log_line(line: &str) -> Option<LogLine<'_>> {
+    let (prefix, rest) = line.split_once("]: ")?;
+    let (before_pid, pid) = prefix.rsplit_once('[')?;
+    let mut words: Vec<&str> = before_pid.split_whitespace().collect();
+    let program = words.pop()?;
+    if !program.eq_ignore_ascii_case("cron") && !program.eq_ignore_ascii_case("crond") {
+        return None;
+    }
+    // The word before the program is the host name.
+    words.pop()?;
+    if words.is_empty() {
+        return None;
+    }
+
+    let rest = rest.trim_end().strip_prefix('(')?;
+    let (user, rest) = rest.split_once(") ")?;
+    let (keyword, message) = rest.split_once(' ').unwrap_or((rest, ""));
+    let message = message
+        .strip_prefix('(')
+        .and_then(|message| message.strip_suffix(')'))
+        .unwrap_or(message);
+
+    Some(LogLine {
+        timestamp: words.join(" "),
+        pid,
+        user,
+        keyword,
+        message,
+    })
+}
+
+/// Collects the runs of `command` from cron log content, newest first.
+///
+/// Cron logs mail and error messages under the PID of the process that ran the job, which is how
+/// they are tied back to the command.
+pub fn parse_history(content: &str, command: &str, limit: usize) -> Vec<HistoryEntry> {
+    let command = command.trim();
+    let mut pids: HashSet<&str> = HashSet::new();
+    let mut entries = Vec::new();
+
+    for line in content.lines().filter_map(parse_log_line) {
+        let kind = match line.keyword {
+            "CMD" if line.message.trim() == command => {
+                pids.insert(line.pid);
+                HistoryKind::Run
+            }
+            "CMD" => {
+                // The PID may be reused by a run of another job.
+                pids.remove(line.pid);
+                continue;
+            }
+            _ if !pids.contains(line.pid) => continue,
+            "MAIL" => HistoryKind::Mail,
+            _ if line.user == "CRON" || line.keyword.eq_ignore_ascii_case("error") => {
+                HistoryKind::Error
+            }
+            _ => continue,
+        };
+
+        let message = match kind {
+            HistoryKind::Run => "Started".to_string(),
+            _ => line.message.to_string(),
+        };
+        entries.push(HistoryEntry {
+            timestamp: line.timestamp,
+            user: line.user.to_string(),
+            kind,
+            message,
+        });
+    }
+
+    entries.reverse();
+    entries.truncate(limit);
+    entries
+}
+
+/// Reads the history of `command` from a log file, `None` when the file does not exist.
+pub fn history_from_file(
+    path: &Path,
+    command: &str,
+    limit: usize,
+) -> io::Result<Option<Vec<HistoryEntry>>> {
+    match fs::read(path) {
+        Ok(content) => Ok(Some(parse_history(
+            &String::from_utf8_lossy(&content),
+            command,
+            limit,
+        ))),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn history_from_journal(command: &str, limit: usize) -> Option<Vec<HistoryEntry>> {
+    JOURNAL_UNITS.iter().find_map(|unit| {
+        let output = Command::new("journalctl")
+            .args(["-u", unit, "-o", "short-iso", "--no-pager", "-q"])
+            .output()
+            .ok()?;
+        if !output.status.success() || output.stdout.is_empty() {
+            return None;
+        }
+        Some(parse_history(
+            &String::from_utf8_lossy(&output.stdout),
+            command,
+            limit,
+        ))
+    })
+}
+
+/// Looks up the past runs of `command` in whichever cron log this host has.
+pub fn read_history(command: &str, limit: usize) -> Result<Vec<HistoryEntry>, String> {
+    let mut errors = Vec::new();
+    for path in LOG_FILES {
+        match history_from_file(Path::new(path), command, limit) {
+            Ok(Some(entries)) => return Ok(entries),
+            Ok(None) => {}
+            Err(e) => errors.push(format!("{}: {}", path, e)),
+        }
+    }
+    if let Some(entries) = history_from_journal(command, limit) {
+        return Ok(entries);
+    }
+
+    if errors.is_empty() {
+        Err("No cron log found in /var/log or the journal".to_string())
+    } else {
+        Err(errors.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    use super::super::wrapper::Wrapper;
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/cron")
+            .join(name)
+    }
+
+    #[test]
+    fn test_parse_log_line() {
+        let line = "Oct 17 03:00:01 web1 CRON[4711]: (root) CMD (/usr/bin/backup --all)";
+        assert_eq!(
+            parse_log_line(line),
+            Some(LogLine {
+                timestamp: "Oct 17 03:00:01".to_string(),
+                pid: "4711",
+                user: "root",
+                keyword: "CMD",
+                message: "/usr/bin/backup --all",
+            })
+        );
+        assert_eq!(
+            parse_log_line("Oct 17 03:00:01 web1 sshd[42]: (root) CMD (ls)"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_syslog_history() {
+        let entries = history_from_file(&fixture("syslog"), "/usr/bin/backup --all", 10)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                HistoryEntry {
+                    timestamp: "Oct 17 03:00:01".to_string(),
+                    user: "CRON".to_string(),
+                    kind: HistoryKind::Error,
+                    message: "grandchild #4802 failed with exit status 1".to_string(),
+                },
+                HistoryEntry {
+                    timestamp: "Oct 17 03:00:01".to_string(),
+                    user: "root".to_string(),
+                    kind: HistoryKind::Run,
+                    message: "Started".to_string(),
+                },
+                HistoryEntry {
+                    timestamp: "Oct 16 03:00:02".to_string(),
+                    user: "root".to_string(),
+                    kind: HistoryKind::Mail,
+                    message: "mailed 42 bytes of output but got status 0x004b from MTA".to_string(),
+                },
+                HistoryEntry {
+                    timestamp: "Oct 16 03:00:01".to_string(),
+                    user: "root".to_string(),
+                    kind: HistoryKind::Run,
+                    message: "Started".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cronie_history_with_limit() {
+        let entries = history_from_file(&fixture("cron"), "run-parts /etc/cron.hourly", 2)
+            .unwrap()
+            .unwrap();
+        let timestamps: Vec<&str> = entries.iter().map(|e| e.timestamp.as_str()).collect();
+        assert_eq!(
+            timestamps,
+            vec!["2026-10-17T12:01:01+02:00", "2026-10-17T11:01:01+02:00"]
+        );
+        assert!(entries.iter().all(|e| e.kind == HistoryKind::Run));
+    }
+
+    #[test]
+    fn test_wrapped_job_history() {
+        let wrapper = Wrapper {
+            lock: Some("/run/lock/backup.lock".to_string()),
+            timeout: Some("30m".to_string()),
+            log: Some("/var/log/backup.log".to_string()),
+        };
+        let command = wrapper.wrap("cd /srv && ./backup.sh");
+        let content = format!(
+            "Oct 17 03:00:01 web1 CRON[4711]: (root) CMD ({})\n\
+             Oct 17 04:00:01 web1 CRON[4712]: (root) CMD (cd /srv && ./backup.sh)\n",
+            command
+        );
+        let entries = parse_history(&content, &command, 10);
+        assert_eq!(
+            entries,
+            vec![HistoryEntry {
+                timestamp: "Oct 17 03:00:01".to_string(),
+                user: "root".to_string(),
+                kind: HistoryKind::Run,
+                message: "Started".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_missing_log_file() {
+        assert_eq!(
+            history_from_file(&fixture("missing"), "true", 10).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use std::fmt;
+use std::path::Path;
+
+use super::{crontab::Crontab, runner::DEFAULT_PATH, CronJob};
+
+/// Shell builtins and keywords that are never looked up on the PATH.
+const BUILTINS: [&str; 12] = [
+    "cd", "exec", "export", "source", ".", "test", "[", "[[", "if", "for", "while", "{",
+];
+/// Interpreters whose first argument is the script that really runs.
+const INTERPRETERS: [&str; 10] = [
+    "sh", "bash", "dash", "zsh", "python", "python3", "perl", "ruby", "php", "node",
+];
+
+/// Something about a job that will probably keep it from working as intended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lint {
+    /// A `%` that cron turns into a newline, cutting the command short.
+    UnescapedPercent,
+    /// A program with a relative path, which resolves against the job's home directory.
+    RelativePath(String),
+    /// A program that is not on the PATH cron runs the job with, and that PATH.
+    NotOnPath(String, String),
+    MissingScript(String),
+    NotExecutable(String),
+    /// The output goes to cron, which mails it to the crontab's owner.
+    OutputNotRedirected,
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Lint::UnescapedPercent => {
+                write!(f, "Unescaped % becomes a newline; write \\% instead")
+            }
+            Lint::RelativePath(program) => {
+                write!(f, "{} is relative to the home directory", program)
+            }
+            Lint::NotOnPath(program, path) => write!(
+                f,
+                "{} is not on the PATH ({}); set PATH or use an absolute path",
+                program, path
+            ),
+            Lint::MissingScript(path) => write!(f, "{} does not exist", path),
+            Lint::NotExecutable(path) => write!(f, "{} is not executable", path),
+            Lint::OutputNotRedirected => write!(
+                f,
+                "Output is not redirected and will be mailed; add >/dev/null 2>&1 or set MAILTO=\"\""
+            ),
+        }
+    }
+}
+
+/// Lints every job of the crontab, in the order of [`Crontab::jobs`].
+pub fn lint_crontab(crontab: &Crontab) -> Vec<Vec<Lint>> {
+    let env: Vec<(&str, &str)> = crontab
+        .env_vars()
+        .map(|var| (var.name.as_str(), var.value.as_str()))
+        .collect();
+    crontab.jobs().map(|job| lint_job(job, &env)).collect()
+}
+
+/// Lints one job, given the environment assignments of its crontab.
+pub fn lint_job(job: &CronJob, env: &[(&str, &str)]) -> Vec<Lint> {
+    let mut lints = Vec::new();
+    let (command, percent) = command_part(&job.job);
+    if percent {
+        lints.push(Lint::UnescapedPercent);
+    }
+
+    let words: Vec<&str> = command
+        .split_whitespace()
+        .skip_while(|word| is_assignment(word))
+        .collect();
+    if let Some(program) = words.first() {
+        lints.extend(lint_program(program, words.get(1).copied(), env));
+    }
+
+    let mail_off = env
+        .iter()
+        .any(|(name, value)| *name == "MAILTO" && value.trim_matches('"').is_empty());
+    // The log option of the wrapper appends the output to a file.
+    if !mail_off && job.wrapper.log.is_none() && !command.contains('>') {
+        lints.push(Lint::OutputNotRedirected);
+    }
+    lints
+}
+
+fn lint_program(program: &str, argument: Option<&str>, env: &[(&str, &str)]) -> Vec<Lint> {
+    // Paths built from variables or `~` can't be checked from here.
+    if BUILTINS.contains(&program) || program.contains(['$', '~', '`']) {
+        return Vec::new();
+    }
+    if program.starts_with('/') {
+        let mut lints = check_file(program, true);
+        let name = program.rsplit('/').next().unwrap_or(program);
+        if lints.is_empty() && INTERPRETERS.contains(&name) {
+            lints.extend(
+                argument
+                    .filter(|a| a.starts_with('/'))
+                    .map(check_script)
+                    .unwrap_or_default(),
+            );
+        }
+        return lints;
+    }
+    if program.contains('/') {
+        return vec![Lint::RelativePath(program.to_string())];
+    }
+
+    let path = env
+        .iter()
+        .rev()
+        .find(|(name, _)| *name == "PATH")
+        .map(|(_, value)| *value)
+        .unwrap_or(DEFAULT_PATH);
+    if !path
+        .split(':')
+        .any(|dir| Path::new(dir).join(program).exists())
+    {
+        return vec![Lint::NotOnPath(program.to_string(), path.to_string())];
+    }
+    if INTERPRETERS.contains(&program) {
+        return argument
+            .filter(|argument| argument.starts_with('/'))
+            .map(check_script)
+            .unwrap_or_default();
+    }
+    Vec::new()
+}
+
+/// A script run through an interpreter only has to exist.
+fn check_script(path: &str) -> Vec<Lint> {
+    check_file(path, false)
+}
+
+fn check_file(path: &str, executable: bool) -> Vec<Lint> {
+    let Ok(metadata) = Path::new(path).metadata() else {
+        return vec![Lint::MissingScript(path.to_string())];
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if executable && metadata.is_file() && metadata.permissions().mode() & 0o111 == 0 {
+            return vec![Lint::NotExecutable(path.to_string())];
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = (metadata, executable);
+    Vec::new()
+}
+
+/// The part of the command that the shell runs, and whether an unescaped `%` cut it off there.
+pub fn command_part(command: &str) -> (&str, bool) {
+    let mut escaped = false;
+    for (i, c) in command.char_indices() {
+        match c {
+            '\\' => escaped = !escaped,
+            '%' if !escaped => return (&command[..i], true),
+            _ => escaped = false,
+        }
+    }
+    (command, false)
+}
+
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+
+    use super::super::wrapper::Wrapper;
+    use super::*;
+
+    fn job(command: &str) -> CronJob {
+        CronJob {
+            cron_notation: "0 * * * *".to_string(),
+            job: command.to_string(),
+            ..CronJob::default()
+        }
+    }
+
+    #[test]
+    fn test_percent_and_redirection() {
+        let quiet = [("MAILTO", "\"\"")];
+        assert_eq!(
+            lint_job(&job("date +%Y-%m-%d > /tmp/today"), &[]),
+            vec![Lint::UnescapedPercent, Lint::OutputNotRedirected]
+        );
+        assert_eq!(
+            lint_job(&job("date +\\%Y-\\%m-\\%d > /tmp/today"), &[]),
+            vec![]
+        );
+        assert_eq!(lint_job(&job("true"), &[]), vec![Lint::OutputNotRedirected]);
+        assert_eq!(lint_job(&job("true"), &quiet), vec![]);
+        assert_eq!(lint_job(&job("true 2>&1 >/dev/null"), &[]), vec![]);
+
+        let logged = CronJob {
+            wrapper: Wrapper {
+                log: Some("/var/log/true.log".to_string()),
+                ..Wrapper::default()
+            },
+            ..job("true")
+        };
+        assert_eq!(lint_job(&logged, &[]), vec![]);
+    }
+
+    #[test]
+    fn test_programs() {
+        let dir = std::env::temp_dir().join(format!("tuixel-lint-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("script.sh");
+        fs::write(&script, "#!/bin/sh\n").unwrap();
+        let script = script.to_string_lossy().to_string();
+        let missing = dir.join("missing.sh").to_string_lossy().to_string();
+        let lint = |command: &str, env: &[(&str, &str)]| {
+            lint_job(&job(&format!("{} >/dev/null", command)), env)
+        };
+
+        assert_eq!(
+            lint(&script, &[]),
+            vec![Lint::NotExecutable(script.clone())]
+        );
+        assert_eq!(lint(&format!("sh {}", script), &[]), vec![]);
+        assert_eq!(
+            lint(&format!("/bin/sh {}", missing), &[]),
+            vec![Lint::MissingScript(missing.clone())]
+        );
+        assert_eq!(
+            lint(&missing, &[]),
+            vec![Lint::MissingScript(missing.clone())]
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+            assert_eq!(lint(&script, &[]), vec![]);
+        }
+
+        assert_eq!(
+            lint("bin/backup.sh", &[]),
+            vec![Lint::RelativePath("bin/backup.sh".to_string())]
+        );
+        assert_eq!(
+            lint("tuixel-surely-missing", &[]),
+            vec![Lint::NotOnPath(
+                "tuixel-surely-missing".to_string(),
+                DEFAULT_PATH.to_string()
+            )]
+        );
+        let missing_here = lint("tuixel-surely-missing", &[("PATH", "/opt/tools")]);
+        assert_eq!(
+            missing_here[0].to_string(),
+            "tuixel-surely-missing is not on the PATH (/opt/tools); set PATH or use an absolute path"
+        );
+        let dir_path = dir.to_string_lossy().to_string();
+        assert_eq!(lint("script.sh", &[("PATH", dir_path.as_str())]), vec![]);
+        assert_eq!(lint("LANG=C cd / && ls", &[]), vec![]);
+        assert_eq!(lint("$HOME/bin/backup", &[]), vec![]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,506 @@
+//! Offline conversion of plain English schedules ("weekdays at 9:30") into cron notation.
+
+const WEEKDAYS: [(&str, u32); 7] = [
+    ("sunday", 0),
+    ("monday", 1),
+    ("tuesday", 2),
+    ("wednesday", 3),
+    ("thursday", 4),
+    ("friday", 5),
+    ("saturday", 6),
+];
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+const ORDINALS: [&str; 4] = ["first", "second", "third", "fourth"];
+
+/// Words that carry no meaning for the schedule.
+const FILLER: [&str; 12] = [
+    "the", "of", "a", "an", "and", "on", "in", "month", "run", "runs", "o'clock", "please",
+];
+
+/// The result of a successful conversion.
+#[derive(Debug, PartialEq)]
+pub struct Conversion {
+    pub expression: String,
+    /// Set when cron can only approximate the phrase and the job needs an extra check.
+    pub note: Option<String>,
+}
+
+#[derive(Default)]
+struct Schedule {
+    every_minute: bool,
+    every_hour: bool,
+    minute_step: Option<u32>,
+    hour_step: Option<u32>,
+    hour_range: Option<(u32, u32)>,
+    times: Vec<(u32, u32)>,
+    day_of_month: Vec<String>,
+    month: Vec<String>,
+    day_of_week: Vec<String>,
+    note: Option<String>,
+    matched: bool,
+}
+
+/// Converts a phrase such as "every 15 minutes" or "first monday of the month at noon" into a
+/// five-field cron expression.
+pub fn natural_to_cron(text: &str) -> Result<Conversion, String> {
+    let normalized = text.to_lowercase().replace([',', '.'], " ");
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+    if tokens.is_empty() {
+        return Err("Describe a schedule, e.g. \"weekdays at 9:30\"".to_string());
+    }
+    if tokens
+        .iter()
+        .any(|token| matches!(*token, "reboot" | "boot" | "startup"))
+    {
+        return Ok(Conversion {
+            expression: "@reboot".to_string(),
+            note: None,
+        });
+    }
+
+    let mut schedule = Schedule::default();
+    let mut i = 0;
+    while i < tokens.len() {
+        i = parse_token(&tokens, i, &mut schedule)?;
+    }
+    if !schedule.matched {
+        return Err(format!("Couldn't find a schedule in \"{}\"", text.trim()));
+    }
+    schedule.into_conversion()
+}
+
+/// Parses the phrase starting at `tokens[i]` and returns the index of the next unread token.
+fn parse_token(tokens: &[&str], i: usize, schedule: &mut Schedule) -> Result<usize, String> {
+    let token = tokens[i];
+    let next = tokens.get(i + 1).copied();
+
+    if token == "every" || token == "each" {
+        schedule.matched = true;
+        return parse_every(tokens, i + 1, schedule);
+    }
+    if token == "at" {
+        let (time, end) = parse_time(tokens, i + 1).ok_or_else(|| {
+            format!(
+                "Expected a time after \"at\", got \"{}\"",
+                next.unwrap_or("")
+            )
+        })?;
+        schedule.times.push(time);
+        schedule.matched = true;
+        return Ok(end);
+    }
+    if token == "between" || token == "from" {
+        let (start, end) = parse_time(tokens, i + 1)
+            .ok_or_else(|| format!("Expected an hour after \"{}\"", token))?;
+        let Some(separator) = tokens
+            .get(end)
+            .filter(|t| matches!(**t, "and" | "to" | "until"))
+        else {
+            return Err(format!(
+                "Expected \"and\" or \"to\" after \"{} {}\"",
+                token, start.0
+            ));
+        };
+        let (stop, end) = parse_time(tokens, end + 1)
+            .ok_or_else(|| format!("Expected an hour after \"{}\"", separator))?;
+        if stop.0 < start.0 {
+            return Err("Hour ranges that wrap past midnight are not supported".to_string());
+        }
+        schedule.hour_range = Some((start.0, stop.0));
+        schedule.matched = true;
+        return Ok(end);
+    }
+    if let Some((time, end)) = parse_named_time(tokens, i) {
+        schedule.times.push(time);
+        schedule.matched = true;
+        return Ok(end);
+    }
+    if let Some(period) = match token {
+        "hourly" => Some("hour"),
+        "daily" => Some("day"),
+        "weekly" => Some("week"),
+        "monthly" => Some("month"),
+        "yearly" | "annually" => Some("year"),
+        _ => None,
+    } {
+        schedule.matched = true;
+        return parse_every(&[period], 0, schedule).map(|_| i + 1);
+    }
+    if let Some(position) = ORDINALS.iter().position(|ordinal| *ordinal == token) {
+        return parse_nth(tokens, i, position as u32, schedule);
+    }
+    if token == "last" {
+        if next.is_some_and(|next| next == "day") {
+            schedule.day_of_month.push("28-31".to_string());
+            schedule.note = Some(
+                "Cron has no \"last day\"; start the job with [ \"$(date -d tomorrow +\\%d)\" = 01 ] &&"
+                    .to_string(),
+            );
+            schedule.matched = true;
+            return Ok(i + 2);
+        }
+        return Err("Only \"last day of the month\" is supported with \"last\"".to_string());
+    }
+    if let Some(days) = weekdays(token) {
+        schedule.day_of_week.push(days);
+        schedule.matched = true;
+        return Ok(i + 1);
+    }
+    if let Some(month) = month(token) {
+        schedule.month.push(month.to_string());
+        schedule.matched = true;
+        return Ok(i + 1);
+    }
+    if let Some(day) = ordinal_day(token) {
+        schedule.day_of_month.push(day.to_string());
+        schedule.matched = true;
+        return Ok(i + 1);
+    }
+    if FILLER.contains(&token) {
+        return Ok(i + 1);
+    }
+    Err(format!("Don't know what \"{}\" means here", token))
+}
+
+/// Parses what follows "every": a unit, a number and a unit, "other" and a unit, or days.
+fn parse_every(tokens: &[&str], i: usize, schedule: &mut Schedule) -> Result<usize, String> {
+    let Some(token) = tokens.get(i).copied() else {
+        return Err("Expected something after \"every\"".to_string());
+    };
+
+    let (step, unit_index) = if token == "other" {
+        (Some(2), i + 1)
+    } else if let Ok(number) = token.parse::<u32>() {
+        (Some(number), i + 1)
+    } else {
+        (None, i)
+    };
+    let Some(unit) = tokens.get(unit_index).copied() else {
+        return Err(format!("Expected a unit after \"every {}\"", token));
+    };
+
+    let end = unit_index + 1;
+    match (unit.trim_end_matches('s'), step) {
+        (_, Some(0)) => Err("The interval has to be at least 1".to_string()),
+        ("minute" | "min" | "m", None) => {
+            schedule.every_minute = true;
+            Ok(end)
+        }
+        ("minute" | "min" | "m", Some(step)) => {
+            if step >= 60 {
+                return Err("Use hours for intervals of 60 minutes or more".to_string());
+            }
+            schedule.minute_step = Some(step);
+            Ok(end)
+        }
+        ("hour" | "hr" | "h", None) => {
+            schedule.every_hour = true;
+            Ok(end)
+        }
+        ("hour" | "hr" | "h", Some(step)) => {
+            if step >= 24 {
+                return Err("Use days for intervals of 24 hours or more".to_string());
+            }
+            schedule.hour_step = Some(step);
+            Ok(end)
+        }
+        ("day", None) => Ok(end),
+        ("day", Some(step)) => {
+            schedule.day_of_month.push(format!("*/{}", step));
+            Ok(end)
+        }
+        ("week", None) => {
+            schedule.day_of_week.push("0".to_string());
+            Ok(end)
+        }
+        ("month", None) => {
+            schedule.day_of_month.push("1".to_string());
+            Ok(end)
+        }
+        ("month", Some(step)) => {
+            schedule.day_of_month.push("1".to_string());
+            schedule.month.push(format!("*/{}", step));
+            Ok(end)
+        }
+        ("year", None) => {
+            schedule.day_of_month.push("1".to_string());
+            schedule.month.push("1".to_string());
+            Ok(end)
+        }
+        (_, None) => match (weekdays(unit), month(unit)) {
+            (Some(days), _) => {
+                schedule.day_of_week.push(days);
+                Ok(end)
+            }
+            (_, Some(month)) => {
+                schedule.month.push(month.to_string());
+                Ok(end)
+            }
+            _ => Err(format!("Don't know how to repeat every \"{}\"", unit)),
+        },
+        (_, Some(_)) => Err(format!("Can't repeat every few \"{}\"", unit)),
+    }
+}
+
+/// Parses "first monday" or "first day" (of the month).
+fn parse_nth(
+    tokens: &[&str],
+    i: usize,
+    position: u32,
+    schedule: &mut Schedule,
+) -> Result<usize, String> {
+    let Some(next) = tokens.get(i + 1).copied() else {
+        return Err(format!("Expected a day after \"{}\"", tokens[i]));
+    };
+    schedule.matched = true;
+    if next == "day" {
+        schedule.day_of_month.push((position + 1).to_string());
+        return Ok(i + 2);
+    }
+
+    let Some((name, weekday)) = WEEKDAYS
+        .iter()
+        .find(|(name, _)| is_weekday_name(next, name))
+    else {
+        return Err(format!(
+            "Expected a day after \"{}\", got \"{}\"",
+            tokens[i], next
+        ));
+    };
+    let first_day = position * 7 + 1;
+    schedule
+        .day_of_month
+        .push(format!("{}-{}", first_day, first_day + 6));
+    // `date +%u` counts Monday as 1 and Sunday as 7.
+    let iso_weekday = if *weekday == 0 { 7 } else { *weekday };
+    schedule.note = Some(format!(
+        "Cron can't combine a day of month with a weekday; this runs on days {}-{}, so start the job with [ \"$(date +\\%u)\" = {} ] && to only run on {}",
+        first_day,
+        first_day + 6,
+        iso_weekday,
+        name
+    ));
+    Ok(i + 2)
+}
+
+/// Parses a time such as `9`, `9am`, `9 pm`, `9:30`, `21:15`, `noon` or `midnight`.
+fn parse_time(tokens: &[&str], i: usize) -> Option<((u32, u32), usize)> {
+    if let Some(named) = parse_named_time(tokens, i) {
+        return Some(named);
+    }
+    let token = *tokens.get(i)?;
+    let (clock, mut suffix) = match token.strip_suffix("am").or(token.strip_suffix("pm")) {
+        Some(clock) => (clock, Some(&token[clock.len()..])),
+        None => (token, None),
+    };
+    let mut end = i + 1;
+    if suffix.is_none() {
+        if let Some(next) = tokens.get(end).filter(|t| matches!(**t, "am" | "pm")) {
+            suffix = Some(*next);
+            end += 1;
+        }
+    }
+
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+        None => (clock.parse::<u32>().ok()?, 0),
+    };
+    let hour = match suffix {
+        Some(_) if hour == 0 || hour > 12 => return None,
+        Some("am") => hour % 12,
+        Some(_) => hour % 12 + 12,
+        None => hour,
+    };
+    (hour < 24 && minute < 60).then_some(((hour, minute), end))
+}
+
+fn parse_named_time(tokens: &[&str], i: usize) -> Option<((u32, u32), usize)> {
+    match *tokens.get(i)? {
+        "noon" | "midday" => Some(((12, 0), i + 1)),
+        "midnight" => Some(((0, 0), i + 1)),
+        _ => None,
+    }
+}
+
+fn is_weekday_name(token: &str, name: &str) -> bool {
+    let token = token.trim_end_matches('s');
+    token == name || (token.len() >= 3 && name.starts_with(token))
+}
+
+/// Day-of-week field for a weekday name (plural allowed), "weekday(s)" or "weekend(s)".
+fn weekdays(token: &str) -> Option<String> {
+    match token.trim_end_matches('s') {
+        "weekday" => Some("1-5".to_string()),
+        "weekend" => Some("0,6".to_string()),
+        _ => WEEKDAYS
+            .iter()
+            .find(|(name, _)| is_weekday_name(token, name))
+            .map(|(_, day)| day.to_string()),
+    }
+}
+
+fn month(token: &str) -> Option<u32> {
+    MONTHS
+        .iter()
+        .position(|name| token == *name || (token.len() == 3 && name.starts_with(token)))
+        .map(|index| index as u32 + 1)
+}
+
+/// Parses "1st", "2nd", "15th" as a day of month.
+fn ordinal_day(token: &str) -> Option<u32> {
+    let number = ["st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| token.strip_suffix(suffix))?;
+    number.parse().ok().filter(|day| (1..=31).contains(day))
+}
+
+impl Schedule {
+    fn into_conversion(self) -> Result<Conversion, String> {
+        let minute = if self.every_minute {
+            "*".to_string()
+        } else if let Some(step) = self.minute_step {
+            format!("*/{}", step)
+        } else if let Some((_, minute)) = self.times.first() {
+            if self.times.iter().any(|(_, other)| other != minute) {
+                return Err("Times with different minutes need separate cron jobs".to_string());
+            }
+            minute.to_string()
+        } else {
+            "0".to_string()
+        };
+
+        let range = self
+            .hour_range
+            .map(|(start, stop)| format!("{}-{}", start, stop));
+        let hour = if let Some(step) = self.hour_step {
+            format!("{}/{}", range.as_deref().unwrap_or("*"), step)
+        } else if let Some(range) = range {
+            range
+        } else if !self.times.is_empty() {
+            let mut hours: Vec<u32> = self.times.iter().map(|(hour, _)| *hour).collect();
+            hours.sort();
+            hours.dedup();
+            join(hours.iter().map(u32::to_string))
+        } else if self.every_minute || self.minute_step.is_some() || self.every_hour {
+            "*".to_string()
+        } else {
+            "0".to_string()
+        };
+
+        let field = |values: Vec<String>| {
+            if values.is_empty() {
+                "*".to_string()
+            } else {
+                join(values.into_iter())
+            }
+        };
+        Ok(Conversion {
+            expression: format!(
+                "{} {} {} {} {}",
+                minute,
+                hour,
+                field(self.day_of_month),
+                field(self.month),
+                field(self.day_of_week)
+            ),
+            note: self.note,
+        })
+    }
+}
+
+fn join(values: impl Iterator<Item = String>) -> String {
+    values.collect::<Vec<String>>().join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn expression(text: &str) -> String {
+        natural_to_cron(text).unwrap().expression
+    }
+
+    #[test]
+    fn test_intervals() {
+        assert_eq!(expression("every minute"), "* * * * *");
+        assert_eq!(expression("every 15 minutes"), "*/15 * * * *");
+        assert_eq!(expression("Every 5 min"), "*/5 * * * *");
+        assert_eq!(expression("every hour"), "0 * * * *");
+        assert_eq!(expression("hourly"), "0 * * * *");
+        assert_eq!(expression("every 2 hours"), "0 */2 * * *");
+        assert_eq!(expression("every other day"), "0 0 */2 * *");
+        assert_eq!(expression("every week"), "0 0 * * 0");
+        assert_eq!(expression("monthly"), "0 0 1 * *");
+        assert_eq!(expression("every year"), "0 0 1 1 *");
+    }
+
+    #[test]
+    fn test_times_and_days() {
+        assert_eq!(expression("weekdays at 9:30"), "30 9 * * 1-5");
+        assert_eq!(expression("every day at 6pm"), "0 18 * * *");
+        assert_eq!(expression("daily at midnight"), "0 0 * * *");
+        assert_eq!(expression("at 7 am and at 7 pm"), "0 7,19 * * *");
+        assert_eq!(
+            expression("every monday and friday at 21:15"),
+            "15 21 * * 1,5"
+        );
+        assert_eq!(expression("on weekends at noon"), "0 12 * * 0,6");
+        assert_eq!(expression("on the 1st and 15th at 10pm"), "0 22 1,15 * *");
+        assert_eq!(expression("every january on the 2nd"), "0 0 2 1 *");
+        assert_eq!(expression("at reboot"), "@reboot");
+    }
+
+    #[test]
+    fn test_hour_ranges() {
+        assert_eq!(expression("every hour between 8 and 18"), "0 8-18 * * *");
+        assert_eq!(
+            expression("every 15 minutes from 9am to 5pm on weekdays"),
+            "*/15 9-17 * * 1-5"
+        );
+        assert_eq!(
+            expression("every 2 hours between 8 and 20"),
+            "0 8-20/2 * * *"
+        );
+    }
+
+    #[test]
+    fn test_nth_weekday_needs_a_note() {
+        let conversion = natural_to_cron("first monday of the month at noon").unwrap();
+        assert_eq!(conversion.expression, "0 12 1-7 * *");
+        assert!(conversion.note.unwrap().contains("\"$(date +\\%u)\" = 1"));
+
+        let conversion = natural_to_cron("last day of the month at 23:00").unwrap();
+        assert_eq!(conversion.expression, "0 23 28-31 * *");
+        assert!(conversion.note.is_some());
+    }
+
+    #[test]
+    fn test_unconvertible_phrases() {
+        assert!(natural_to_cron("").is_err());
+        assert_eq!(
+            natural_to_cron("whenever you feel like it").unwrap_err(),
+            "Don't know what \"whenever\" means here"
+        );
+        assert!(natural_to_cron("every 90 minutes").is_err());
+        assert!(natural_to_cron("at 25:00").is_err());
+        assert!(natural_to_cron("at 9:00 and 17:30").is_err());
+        assert!(natural_to_cron("the").is_err());
+    }
+}
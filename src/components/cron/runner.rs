@@ -0,0 +1,316 @@
+use std::fs;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Instant;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+
+use crate::action::Action;
+
+use super::source::current_username;
+
+const DEFAULT_SHELL: &str = "/bin/sh";
+/// The PATH cron gives jobs when the crontab does not set one.
+pub const DEFAULT_PATH: &str = "/usr/bin:/bin";
+/// Where util-linux installs `runuser`, which is not on the PATH of jobs.
+const RUNUSER: [&str; 3] = ["/usr/sbin/runuser", "/sbin/runuser", "/usr/bin/runuser"];
+
+/// The user "run now" has to switch to for a job owned by `owner`, `None` when the job can run
+/// as the user running tuixel. Only root can switch, so other users get an error instead.
+pub fn run_as(owner: Option<&str>) -> Result<Option<String>, String> {
+    match owner {
+        None => Ok(None),
+        Some(owner) if owner.is_empty() || owner == current_username() => Ok(None),
+        Some(owner) if unsafe { libc::geteuid() } == 0 => Ok(Some(owner.to_string())),
+        Some(owner) => Err(format!(
+            "This job runs as {}; only root can run it now for them",
+            owner
+        )),
+    }
+}
+
+/// Runs a crontab command in the background the way cron would, streaming its output back as
+/// `JobStdout`/`JobStderr` actions and finishing with `JobFinished`.
+///
+/// With `user` set the command runs as that user through `runuser`, see [`run_as`].
+/// Aborting the returned handle kills the command.
+pub fn spawn_job(
+    command: &str,
+    crontab_env: &[(String, String)],
+    user: Option<&str>,
+    tx: UnboundedSender<Action>,
+) -> JoinHandle<()> {
+    let (command, input) = split_command(command);
+    let env = job_environment(crontab_env, user);
+    let user = user.map(str::to_string);
+
+    tokio::spawn(async move {
+        let started = Instant::now();
+        let shell = env
+            .iter()
+            .find(|(name, _)| name == "SHELL")
+            .map(|(_, value)| value.clone())
+            .unwrap_or_else(|| DEFAULT_SHELL.to_string());
+        let home = env
+            .iter()
+            .find(|(name, _)| name == "HOME")
+            .map(|(_, value)| value.clone())
+            .unwrap_or_else(|| "/".to_string());
+
+        let mut process = match &user {
+            Some(user) => {
+                let runuser = RUNUSER
+                    .into_iter()
+                    .find(|path| Path::new(path).exists())
+                    .unwrap_or("runuser");
+                let mut process = Command::new(runuser);
+                // -m keeps the environment of the job rather than the user's login one.
+                process.args(["-m", "-u", user, "--", &shell]);
+                process
+            }
+            None => Command::new(&shell),
+        };
+        let spawned = process
+            .arg("-c")
+            .arg(&command)
+            .env_clear()
+            .envs(env)
+            .current_dir(home)
+            .stdin(if input.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn();
+
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(e) => {
+                let _ = tx.send(Action::JobStderr(format!(
+                    "Failed to start {}: {}",
+                    shell, e
+                )));
+                let _ = tx.send(Action::JobFinished(None, started.elapsed()));
+                return;
+            }
+        };
+
+        let stdin = child.stdin.take();
+        let write_input = async move {
+            if let (Some(mut stdin), Some(input)) = (stdin, input) {
+                let _ = stdin.write_all(input.as_bytes()).await;
+            }
+        };
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        tokio::join!(
+            write_input,
+            forward_lines(stdout, &tx, Action::JobStdout),
+            forward_lines(stderr, &tx, Action::JobStderr),
+        );
+
+        let code = match child.wait().await {
+            Ok(status) => status.code(),
+            Err(e) => {
+                let _ = tx.send(Action::JobStderr(format!("Failed to wait for job: {}", e)));
+                None
+            }
+        };
+        let _ = tx.send(Action::JobFinished(code, started.elapsed()));
+    })
+}
+
+async fn forward_lines<R: AsyncRead + Unpin>(
+    reader: Option<R>,
+    tx: &UnboundedSender<Action>,
+    action: fn(String) -> Action,
+) {
+    let Some(reader) = reader else {
+        return;
+    };
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if tx.send(action(line)).is_err() {
+            break;
+        }
+    }
+}
+
+/// Applies cron's `%` handling: the first unescaped `%` ends the command and everything after it
+/// is fed to stdin, with further `%` turned into newlines. `\%` stands for a literal `%`.
+pub fn split_command(job: &str) -> (String, Option<String>) {
+    let mut command = String::new();
+    let mut input: Option<String> = None;
+    let mut chars = job.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '%' && input.is_none() {
+            input = Some(String::new());
+            continue;
+        }
+        let target = input.as_mut().unwrap_or(&mut command);
+        match c {
+            '\\' if chars.peek() == Some(&'%') => {
+                target.push('%');
+                chars.next();
+            }
+            '%' => target.push('\n'),
+            _ => target.push(c),
+        }
+    }
+
+    let input = input.map(|mut input| {
+        if !input.ends_with('\n') {
+            input.push('\n');
+        }
+        input
+    });
+    (command, input)
+}
+
+/// The minimal environment cron gives a job of `user` (the user running tuixel when `None`),
+/// overridden by the crontab's own variables.
+pub fn job_environment(
+    crontab_env: &[(String, String)],
+    user: Option<&str>,
+) -> Vec<(String, String)> {
+    let (user, home) = match user {
+        Some(user) => {
+            let home = fs::read_to_string("/etc/passwd")
+                .ok()
+                .and_then(|passwd| home_directory(&passwd, user))
+                .unwrap_or_else(|| "/".to_string());
+            (user.to_string(), home)
+        }
+        None => (
+            current_username(),
+            std::env::var("HOME").unwrap_or_else(|_| "/".to_string()),
+        ),
+    };
+    let mut env = vec![
+        ("SHELL".to_string(), DEFAULT_SHELL.to_string()),
+        ("PATH".to_string(), DEFAULT_PATH.to_string()),
+        ("HOME".to_string(), home),
+        ("LOGNAME".to_string(), user.clone()),
+        ("USER".to_string(), user),
+    ];
+
+    for (name, value) in crontab_env {
+        match env.iter_mut().find(|(existing, _)| existing == name) {
+            Some(existing) => existing.1 = value.clone(),
+            None => env.push((name.clone(), value.clone())),
+        }
+    }
+    env
+}
+
+/// The home directory of `user` in the content of a passwd file.
+fn home_directory(passwd: &str, user: &str) -> Option<String> {
+    passwd.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split(':').collect();
+        (fields.len() >= 7 && fields[0] == user).then(|| fields[5].to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    #[test]
+    fn test_split_command() {
+        assert_eq!(
+            split_command("/usr/bin/backup --all"),
+            ("/usr/bin/backup --all".to_string(), None)
+        );
+        assert_eq!(
+            split_command("date +\\%Y-\\%m-\\%d"),
+            ("date +%Y-%m-%d".to_string(), None)
+        );
+        assert_eq!(
+            split_command("mail -s hi root%line one%line two"),
+            (
+                "mail -s hi root".to_string(),
+                Some("line one\nline two\n".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn test_job_environment() {
+        let env = job_environment(
+            &[
+                ("PATH".to_string(), "/opt/bin".to_string()),
+                ("MAILTO".to_string(), "ops@example.com".to_string()),
+            ],
+            None,
+        );
+        let get = |name: &str| {
+            env.iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(get("SHELL"), Some("/bin/sh"));
+        assert_eq!(get("PATH"), Some("/opt/bin"));
+        assert_eq!(get("MAILTO"), Some("ops@example.com"));
+    }
+
+    #[test]
+    fn test_run_as() {
+        let passwd = fs::read_to_string(
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/users/passwd"),
+        )
+        .unwrap();
+        assert_eq!(
+            home_directory(&passwd, "shop"),
+            Some("/home/shop".to_string())
+        );
+        assert_eq!(home_directory(&passwd, "broken line"), None);
+
+        assert_eq!(run_as(None), Ok(None));
+        assert_eq!(run_as(Some(&current_username())), Ok(None));
+        let other = run_as(Some("tuixel-someone-else"));
+        if unsafe { libc::geteuid() } == 0 {
+            assert_eq!(other, Ok(Some("tuixel-someone-else".to_string())));
+        } else {
+            assert!(other.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spawn_job_streams_output() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        spawn_job(
+            "echo $GREETING; echo oops >&2; cat; exit 3%piped",
+            &[("GREETING".to_string(), "hello".to_string())],
+            None,
+            tx,
+        )
+        .await
+        .unwrap();
+
+        let mut stdout = vec![];
+        let mut stderr = vec![];
+        let mut code = None;
+        while let Some(action) = rx.recv().await {
+            match action {
+                Action::JobStdout(line) => stdout.push(line),
+                Action::JobStderr(line) => stderr.push(line),
+                Action::JobFinished(exit_code, _) => code = exit_code,
+                _ => {}
+            }
+        }
+        assert_eq!(stdout, vec!["hello", "piped"]);
+        assert_eq!(stderr, vec!["oops"]);
+        assert_eq!(code, Some(3));
+    }
+}
@@ -0,0 +1,311 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+const SYSTEM_CRONTAB: &str = "/etc/crontab";
+const SYSTEM_CRON_DIR: &str = "/etc/cron.d";
+const SPOOL_DIRS: [&str; 2] = ["/var/spool/cron/crontabs", "/var/spool/cron"];
+/// The UIDs `useradd` gives regular accounts, unless `/etc/login.defs` says otherwise.
+const UID_MIN: u32 = 1000;
+const UID_MAX: u32 = 60000;
+
+/// Where a crontab lives and how it has to be read and written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum CrontabSource {
+    /// The crontab of the user running tuixel (`crontab -l`).
+    #[default]
+    CurrentUser,
+    /// Another user's crontab (`crontab -u <user> -l`).
+    User(String),
+    /// `/etc/crontab` or a file in `/etc/cron.d`, which carry an extra user column.
+    System(PathBuf),
+}
+
+impl CrontabSource {
+    pub fn is_system(&self) -> bool {
+        matches!(self, CrontabSource::System(_))
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            CrontabSource::CurrentUser => format!("{} (current user)", current_username()),
+            CrontabSource::User(user) => format!("{} (user crontab)", user),
+            CrontabSource::System(path) => path.display().to_string(),
+        }
+    }
+
+    pub fn read(&self) -> io::Result<Option<String>> {
+        match self {
+            CrontabSource::System(path) => match fs::read_to_string(path) {
+                Ok(content) => Ok(Some(content)),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e),
+            },
+            _ => {
+                let output = self
+                    .crontab_command()
+                    .arg("-l")
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped())
+                    .output()?;
+
+                if !output.status.success() {
+                    let stderr_output = String::from_utf8_lossy(&output.stderr);
+                    if stderr_output.contains("no crontab for") {
+                        return Ok(None);
+                    }
+                    return Err(io::Error::other(format!(
+                        "Failed to read crontab: {}",
+                        stderr_output.trim()
+                    )));
+                }
+
+                Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()))
+            }
+        }
+    }
+
+    pub fn write(&self, content: &str) -> io::Result<()> {
+        match self {
+            CrontabSource::System(path) => write_atomically(path, content),
+            _ => {
+                let mut process = self
+                    .crontab_command()
+                    .arg("-")
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::piped())
+                    .spawn()?;
+
+                // Dropping stdin closes it, so that crontab reads the whole file and exits.
+                if let Some(mut stdin) = process.stdin.take() {
+                    stdin.write_all(content.as_bytes())?;
+                }
+
+                let output = process.wait_with_output()?;
+                if !output.status.success() {
+                    return Err(io::Error::other(crontab_failure(&output)));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn crontab_command(&self) -> Command {
+        let mut command = Command::new("crontab");
+        if let CrontabSource::User(user) = self {
+            command.arg("-u").arg(user);
+        }
+        command
+    }
+}
+
+/// Describes a failed `crontab` run by its exit status and what it printed to stderr,
+/// e.g. "crontab exited with status 1: "-":1: bad minute".
+fn crontab_failure(output: &Output) -> String {
+    let status = match output.status.code() {
+        Some(code) => format!("crontab exited with status {}", code),
+        None => "crontab was killed by a signal".to_string(),
+    };
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stderr = stderr.trim();
+    if stderr.is_empty() {
+        status
+    } else {
+        format!(
+            "{}: {}",
+            status,
+            stderr.lines().collect::<Vec<_>>().join(" ")
+        )
+    }
+}
+
+/// Lists every crontab tuixel knows how to manage on this host.
+///
+/// Per-user crontabs are taken from the cron spool when it is readable (i.e. when running as
+/// root), otherwise from root and the regular accounts in `/etc/passwd`.
+pub fn list_crontab_sources() -> Vec<CrontabSource> {
+    let current = current_username();
+    let mut sources = vec![CrontabSource::CurrentUser];
+
+    let mut users = spool_users(&SPOOL_DIRS).unwrap_or_else(passwd_users);
+    users.sort();
+    users.dedup();
+    sources.extend(
+        users
+            .into_iter()
+            .filter(|user| *user != current)
+            .map(CrontabSource::User),
+    );
+
+    if Path::new(SYSTEM_CRONTAB).exists() {
+        sources.push(CrontabSource::System(PathBuf::from(SYSTEM_CRONTAB)));
+    }
+    sources.extend(
+        system_cron_files(Path::new(SYSTEM_CRON_DIR))
+            .into_iter()
+            .map(CrontabSource::System),
+    );
+
+    sources
+}
+
+/// Files in `/etc/cron.d` that cron itself would load.
+fn system_cron_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            !name.starts_with('.')
+                && !name.ends_with('~')
+                && !name.contains(".dpkg-")
+                && !name.ends_with(".rpmsave")
+                && !name.ends_with(".rpmnew")
+                && name != "placeholder"
+        })
+        .map(|entry| entry.path())
+        .collect();
+    files.sort();
+    files
+}
+
+/// The owners of the crontabs in the first of `dirs` that has any. An empty spool, as on
+/// hosts where the other directory is the one cron uses, does not count.
+fn spool_users(dirs: &[&str]) -> Option<Vec<String>> {
+    dirs.iter().find_map(|dir| {
+        let entries = fs::read_dir(dir).ok()?;
+        let users: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().map(|t| t.is_file()).unwrap_or(false))
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| !name.starts_with('.') && !name.starts_with("tmp."))
+            .collect();
+        Some(users).filter(|users| !users.is_empty())
+    })
+}
+
+fn passwd_users() -> Vec<String> {
+    let login_defs = fs::read_to_string("/etc/login.defs").unwrap_or_default();
+    let uids = (
+        login_defs_value(&login_defs, "UID_MIN").unwrap_or(UID_MIN),
+        login_defs_value(&login_defs, "UID_MAX").unwrap_or(UID_MAX),
+    );
+    fs::read_to_string("/etc/passwd")
+        .map(|content| login_users(&content, uids))
+        .unwrap_or_default()
+}
+
+/// Root and the regular accounts of a passwd file, leaving out `daemon`, `nobody` and the other
+/// service accounts, whose UIDs are outside `uids`.
+fn login_users(passwd: &str, (uid_min, uid_max): (u32, u32)) -> Vec<String> {
+    passwd
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(':').collect();
+            let uid: u32 = fields.get(2)?.parse().ok()?;
+            let regular = uid == 0 || (uid_min..=uid_max).contains(&uid);
+            (regular && !fields[0].is_empty()).then(|| fields[0].to_string())
+        })
+        .collect()
+}
+
+fn login_defs_value(login_defs: &str, key: &str) -> Option<u32> {
+    login_defs.lines().find_map(|line| {
+        let mut words = line.split_whitespace();
+        (words.next() == Some(key))
+            .then(|| words.next()?.parse().ok())
+            .flatten()
+    })
+}
+
+pub fn current_username() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("LOGNAME"))
+        .unwrap_or_else(|_| {
+            if unsafe { libc::geteuid() } == 0 {
+                "root".to_string()
+            } else {
+                "unknown".to_string()
+            }
+        })
+}
+
+pub fn write_atomically(path: &Path, content: &str) -> io::Result<()> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp_path = path.with_file_name(format!(".{}.tuixel", file_name));
+
+    fs::write(&tmp_path, content)?;
+    if let Ok(metadata) = fs::metadata(path) {
+        fs::set_permissions(&tmp_path, metadata.permissions())?;
+    }
+    fs::rename(&tmp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn run(script: &str) -> Output {
+        Command::new("sh").arg("-c").arg(script).output().unwrap()
+    }
+
+    #[test]
+    fn test_login_users() {
+        let passwd = fs::read_to_string(
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/users/passwd"),
+        )
+        .unwrap();
+        assert_eq!(
+            login_users(&passwd, (UID_MIN, UID_MAX)),
+            vec!["root", "shop", "blog"]
+        );
+        assert_eq!(
+            login_defs_value("# UID_MIN 10\nUID_MIN\t\t  500\nUID_MAX 6000\n", "UID_MIN"),
+            Some(500)
+        );
+        assert_eq!(login_defs_value("", "UID_MIN"), None);
+    }
+
+    #[test]
+    fn test_spool_users() {
+        let root = std::env::temp_dir().join(format!("tuixel-spool-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (empty, spool) = (root.join("crontabs"), root.join("cron"));
+        fs::create_dir_all(&empty).unwrap();
+        fs::create_dir_all(&spool).unwrap();
+        let dirs = [empty.to_str().unwrap(), spool.to_str().unwrap()];
+        assert_eq!(spool_users(&dirs), None);
+
+        fs::write(spool.join("shop"), "").unwrap();
+        fs::write(spool.join(".placeholder"), "").unwrap();
+        assert_eq!(spool_users(&dirs), Some(vec!["shop".to_string()]));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_crontab_failure() {
+        assert_eq!(
+            crontab_failure(&run(
+                "echo '\"-\":1: bad minute' >&2; echo 'errors in crontab file' >&2; exit 1"
+            )),
+            "crontab exited with status 1: \"-\":1: bad minute errors in crontab file"
+        );
+        assert_eq!(
+            crontab_failure(&run("exit 2")),
+            "crontab exited with status 2"
+        );
+    }
+}
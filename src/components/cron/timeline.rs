@@ -0,0 +1,167 @@
+use chrono::{DateTime, Duration, Timelike};
+use chrono_tz::Tz;
+
+use super::{
+    utils::{next_run, parse_timezone},
+    CronJob,
+};
+
+/// How much time a timeline covers and how finely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineSpan {
+    /// 24 hours, one slot per minute.
+    Day,
+    /// 7 days, one slot per hour.
+    Week,
+}
+
+impl TimelineSpan {
+    pub fn slot_minutes(self) -> i64 {
+        match self {
+            TimelineSpan::Day => 1,
+            TimelineSpan::Week => 60,
+        }
+    }
+
+    pub fn slots(self) -> usize {
+        match self {
+            TimelineSpan::Day => 24 * 60,
+            TimelineSpan::Week => 7 * 24,
+        }
+    }
+}
+
+/// The jobs that fire in each slot of a span of time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timeline {
+    pub span: TimelineSpan,
+    /// Start of the first slot, on a full hour.
+    pub start: DateTime<Tz>,
+    /// Indices of the jobs firing in each slot, each job at most once per slot.
+    pub slots: Vec<Vec<usize>>,
+}
+
+impl Timeline {
+    /// Lays out the runs of `jobs` from the start of the hour of `now`, in the zone of `now`.
+    /// Jobs with their own `CRON_TZ` are scheduled in that zone.
+    pub fn new(jobs: &[&CronJob], span: TimelineSpan, now: DateTime<Tz>) -> Self {
+        let start = now
+            .with_minute(0)
+            .and_then(|start| start.with_second(0))
+            .and_then(|start| start.with_nanosecond(0))
+            .unwrap_or(now);
+        let end = slot_start_of(start, span, span.slots());
+        let mut slots = vec![Vec::new(); span.slots()];
+
+        for (index, job) in jobs.iter().enumerate() {
+            if job.disabled {
+                continue;
+            }
+            let zone = match job.timezone.as_str() {
+                "" => start.timezone(),
+                zone => match parse_timezone(zone) {
+                    Ok(zone) => zone,
+                    Err(_) => continue,
+                },
+            };
+            // Just before the start, so that a run right at the start counts.
+            let mut after = (start - Duration::seconds(1)).with_timezone(&zone);
+            while let Ok(Some(run)) = next_run(&job.cron_notation, &after) {
+                if run >= end {
+                    break;
+                }
+                let slot_index = ((run - start).num_minutes() / span.slot_minutes()) as usize;
+                slots[slot_index].push(index);
+                // Further runs in the same slot don't matter, so skip to its last second.
+                let slot_end = slot_start_of(start, span, slot_index + 1) - Duration::seconds(1);
+                after = slot_end.with_timezone(&zone).max(run);
+            }
+        }
+
+        Self { span, start, slots }
+    }
+
+    pub fn slot_start(&self, slot: usize) -> DateTime<Tz> {
+        slot_start_of(self.start, self.span, slot)
+    }
+
+    /// The slots with the most jobs, busiest first, leaving out slots with fewer than `min` jobs.
+    pub fn busiest(&self, min: usize, count: usize) -> Vec<usize> {
+        let mut busy: Vec<usize> = (0..self.slots.len())
+            .filter(|&slot| self.slots[slot].len() >= min)
+            .collect();
+        busy.sort_by_key(|&slot| std::cmp::Reverse(self.slots[slot].len()));
+        busy.truncate(count);
+        busy
+    }
+}
+
+fn slot_start_of(start: DateTime<Tz>, span: TimelineSpan, slot: usize) -> DateTime<Tz> {
+    start + Duration::minutes(span.slot_minutes() * slot as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn job(notation: &str) -> CronJob {
+        CronJob {
+            cron_notation: notation.to_string(),
+            ..CronJob::default()
+        }
+    }
+
+    #[test]
+    fn test_day_timeline() {
+        let hourly = job("0 * * * *");
+        let nightly = job("0 3 * * *");
+        let frequent = job("*/15 * * * *");
+        let reboot = job("@reboot");
+        let disabled = CronJob {
+            disabled: true,
+            ..job("0 * * * *")
+        };
+        let tokyo = CronJob {
+            timezone: "Asia/Tokyo".to_string(),
+            ..job("0 12 * * *")
+        };
+        let jobs = [&hourly, &nightly, &frequent, &reboot, &disabled, &tokyo];
+        let now = Tz::UTC.with_ymd_and_hms(2025, 3, 1, 2, 40, 0).unwrap();
+
+        let timeline = Timeline::new(&jobs, TimelineSpan::Day, now);
+        assert_eq!(
+            timeline.start,
+            Tz::UTC.with_ymd_and_hms(2025, 3, 1, 2, 0, 0).unwrap()
+        );
+        assert_eq!(timeline.slots.len(), 24 * 60);
+        assert_eq!(timeline.slots[0], vec![0, 2]);
+        assert_eq!(timeline.slots[15], vec![2]);
+        // Noon in Tokyo is 03:00 UTC.
+        assert_eq!(timeline.slots[60], vec![0, 1, 2, 5]);
+        assert_eq!(timeline.slots[60 * 22], vec![0, 2]);
+        assert_eq!(timeline.slots[61], Vec::<usize>::new());
+        assert_eq!(timeline.busiest(3, 5), vec![60]);
+    }
+
+    #[test]
+    fn test_week_timeline() {
+        let weekly = job("30 6 * * 1");
+        let frequent = job("*/10 * * * *");
+        let jobs = [&weekly, &frequent];
+        // A Saturday.
+        let now = Tz::UTC.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap();
+
+        let timeline = Timeline::new(&jobs, TimelineSpan::Week, now);
+        assert_eq!(timeline.slots.len(), 7 * 24);
+        assert_eq!(timeline.slots[0], vec![1]);
+        assert_eq!(timeline.slots[2 * 24 + 6], vec![0, 1]);
+        assert_eq!(
+            timeline.slot_start(2 * 24 + 6),
+            Tz::UTC.with_ymd_and_hms(2025, 3, 3, 6, 0, 0).unwrap()
+        );
+        assert_eq!(timeline.busiest(2, 3), vec![2 * 24 + 6]);
+    }
+}
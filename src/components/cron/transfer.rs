@@ -0,0 +1,512 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    crontab::{Crontab, CRON_TZ},
+    lint::command_part,
+    utils::{parse_timezone, validate_cron_expression},
+    wrapper::Wrapper,
+    CronJob,
+};
+use crate::config::CronSettings;
+
+/// The jobs and environment of a crontab as they are exported to and imported from files.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrontabFile {
+    /// Assignments other than `CRON_TZ`, which is kept per job instead.
+    #[serde(default)]
+    pub env: Vec<EnvEntry>,
+    #[serde(default)]
+    pub jobs: Vec<JobEntry>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvEntry {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobEntry {
+    pub schedule: String,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    /// Only used by system crontabs.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub user: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub timezone: String,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+/// `text` with its line breaks shown as `\n`, to name an entry within a list of lines.
+fn one_line(text: &str) -> String {
+    text.replace('\n', "\\n")
+}
+
+impl JobEntry {
+    fn from_job(job: &CronJob) -> Self {
+        Self {
+            schedule: job.cron_notation.clone(),
+            command: job.command(),
+            description: job.job_description.clone(),
+            enabled: !job.disabled,
+            user: job.user.clone(),
+            timezone: job.timezone.clone(),
+        }
+    }
+
+    fn same_job(&self, job: &CronJob) -> bool {
+        self.command == job.command() && self.user == job.user
+    }
+
+    /// An empty timezone is the same as `default_zone`.
+    fn same_settings(&self, job: &CronJob, default_zone: &str) -> bool {
+        let zone = |zone: &str| {
+            if zone.is_empty() {
+                default_zone.to_string()
+            } else {
+                zone.to_string()
+            }
+        };
+        self.schedule == job.cron_notation
+            && self.description == job.job_description
+            && self.enabled != job.disabled
+            && zone(&self.timezone) == zone(&job.timezone)
+    }
+
+    fn label(&self) -> String {
+        format!("{} {}", self.schedule, self.command)
+    }
+
+    /// Why the entry can't become a crontab line, if it can't.
+    fn validate(&self, settings: &CronSettings) -> Result<(), String> {
+        validate_cron_expression(&self.schedule, settings.seconds)?;
+        parse_timezone(&self.timezone)?;
+        if self.command.trim().is_empty() {
+            return Err("The command is empty".to_string());
+        }
+        if self.command.contains('\n') || self.description.contains('\n') {
+            return Err("A crontab entry can't span lines".to_string());
+        }
+        if command_part(&self.command).1 {
+            return Err("An unescaped % ends the command there; write \\% instead".to_string());
+        }
+        if self.user.contains(char::is_whitespace) {
+            return Err(format!("Invalid user {:?}", self.user));
+        }
+        Ok(())
+    }
+}
+
+impl EnvEntry {
+    fn validate(&self) -> Result<(), String> {
+        let valid_name = !self.name.is_empty()
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_name {
+            return Err(format!("Invalid variable name {:?}", self.name));
+        }
+        if self.value.contains('\n') {
+            return Err("A value can't span lines".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Json,
+    Yaml,
+}
+
+impl FileFormat {
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Ok(FileFormat::Json),
+            Some("yaml" | "yml") => Ok(FileFormat::Yaml),
+            _ => Err("Use a .json, .yaml or .yml file".to_string()),
+        }
+    }
+}
+
+impl CrontabFile {
+    pub fn from_crontab(crontab: &Crontab) -> Self {
+        Self {
+            env: crontab
+                .env_vars()
+                .filter(|var| var.name != CRON_TZ)
+                .map(|var| EnvEntry {
+                    name: var.name.clone(),
+                    value: var.value.clone(),
+                })
+                .collect(),
+            jobs: crontab.jobs().map(JobEntry::from_job).collect(),
+        }
+    }
+
+    pub fn to_string(&self, format: FileFormat) -> Result<String, String> {
+        match format {
+            FileFormat::Json => serde_json::to_string_pretty(self).map_err(|e| e.to_string()),
+            FileFormat::Yaml => serde_yaml::to_string(self).map_err(|e| e.to_string()),
+        }
+    }
+
+    pub fn from_str(content: &str, format: FileFormat) -> Result<Self, String> {
+        match format {
+            FileFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+            FileFormat::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Writes the file in the format its extension asks for.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let content = self.to_string(FileFormat::from_path(path)?)?;
+        fs::write(path, content).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// One message per entry that can't be imported.
+    fn errors(&self, settings: &CronSettings) -> Vec<String> {
+        let env = self.env.iter().enumerate().filter_map(|(i, var)| {
+            var.validate()
+                .err()
+                .map(|e| format!("Variable {} ({}): {}", i + 1, one_line(&var.name), e))
+        });
+        let jobs = self.jobs.iter().enumerate().filter_map(|(i, entry)| {
+            entry
+                .validate(settings)
+                .err()
+                .map(|e| format!("Job {} ({}): {}", i + 1, one_line(&entry.label()), e))
+        });
+        env.chain(jobs).collect()
+    }
+
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let format = FileFormat::from_path(path)?;
+        let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Self::from_str(&content, format).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+/// What importing a file would do to a crontab.
+///
+/// Jobs are matched by command and user; a matched job whose schedule, description, state or
+/// timezone differs is changed, unmatched jobs of the file are added and unmatched jobs of the
+/// crontab are removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportPlan {
+    pub added: Vec<JobEntry>,
+    /// Indices into the crontab's jobs, with what they become.
+    pub changed: Vec<(usize, JobEntry)>,
+    pub removed: Vec<usize>,
+    /// The new assignments, when they differ.
+    pub env: Option<Vec<(String, String)>>,
+}
+
+impl ImportPlan {
+    /// `system` tells whether jobs without a user go to root, as in system crontabs.
+    ///
+    /// A file with any invalid entry is refused as a whole, listing every problem on its own
+    /// line.
+    pub fn new(
+        crontab: &Crontab,
+        file: &CrontabFile,
+        system: bool,
+        settings: &CronSettings,
+    ) -> Result<Self, String> {
+        let errors = file.errors(settings);
+        if !errors.is_empty() {
+            let mut lines = vec!["Nothing was imported:".to_string()];
+            lines.extend(errors);
+            return Err(lines.join("\n"));
+        }
+
+        let jobs: Vec<&CronJob> = crontab.jobs().collect();
+        let default_zone = crontab.default_zone();
+        let mut matched = vec![false; jobs.len()];
+        let mut plan = ImportPlan::default();
+
+        for entry in &file.jobs {
+            let mut entry = entry.clone();
+            if system && entry.user.is_empty() {
+                entry.user = "root".to_string();
+            } else if !system {
+                entry.user.clear();
+            }
+            let found = (0..jobs.len()).find(|&i| !matched[i] && entry.same_job(jobs[i]));
+            match found {
+                Some(i) => {
+                    matched[i] = true;
+                    if !entry.same_settings(jobs[i], &default_zone) {
+                        plan.changed.push((i, entry));
+                    }
+                }
+                None => plan.added.push(entry),
+            }
+        }
+        plan.removed = (0..jobs.len()).filter(|&i| !matched[i]).collect();
+
+        let current: Vec<(String, String)> = crontab
+            .env_vars()
+            .filter(|var| var.name != CRON_TZ)
+            .map(|var| (var.name.clone(), var.value.clone()))
+            .collect();
+        let wanted: Vec<(String, String)> = file
+            .env
+            .iter()
+            .filter(|var| var.name != CRON_TZ)
+            .map(|var| (var.name.clone(), var.value.clone()))
+            .collect();
+        if current != wanted {
+            plan.env = Some(wanted);
+        }
+        Ok(plan)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.changed.is_empty()
+            && self.removed.is_empty()
+            && self.env.is_none()
+    }
+
+    /// One line per change, prefixed with `+`, `~` or `-`.
+    pub fn summary(&self, crontab: &Crontab) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        lines.extend(
+            self.added
+                .iter()
+                .map(|entry| format!("+ {}", entry.label())),
+        );
+        lines.extend(self.changed.iter().map(|(i, entry)| {
+            let before = crontab
+                .job(*i)
+                .map(|job| format!("{} {}", job.cron_notation, job.command()))
+                .unwrap_or_default();
+            format!("~ {}  →  {}", before, entry.label())
+        }));
+        lines.extend(self.removed.iter().filter_map(|&i| {
+            crontab
+                .job(i)
+                .map(|job| format!("- {} {}", job.cron_notation, job.command()))
+        }));
+        if let Some(env) = &self.env {
+            let names: Vec<&str> = env.iter().map(|(name, _)| name.as_str()).collect();
+            lines.push(format!("~ Environment: {}", names.join(", ")));
+        }
+        lines
+    }
+
+    pub fn apply(&self, crontab: &mut Crontab) {
+        for (i, entry) in &self.changed {
+            if let Some(job) = crontab.job_mut(*i) {
+                job.cron_notation = entry.schedule.clone();
+                job.job_description = entry.description.clone();
+                job.disabled = !entry.enabled;
+            }
+            crontab.set_job_timezone(*i, &entry.timezone);
+        }
+        for &i in self.removed.iter().rev() {
+            crontab.remove_job(i);
+        }
+        if !self.removed.is_empty() {
+            crontab.prune_zones();
+        }
+        if let Some(env) = &self.env {
+            // `CRON_TZ` lines belong to the jobs and stay where they are.
+            let mut vars = env.clone();
+            vars.extend(
+                crontab
+                    .env_vars()
+                    .filter(|var| var.name == CRON_TZ)
+                    .map(|var| (var.name.clone(), var.value.clone())),
+            );
+            crontab.set_env_vars(&vars);
+        }
+        for entry in &self.added {
+            let (command, wrapper) = Wrapper::unwrap(&entry.command);
+            crontab.push_job(CronJob::new(CronJob {
+                cron_notation: entry.schedule.clone(),
+                job: command,
+                wrapper,
+                job_description: entry.description.clone(),
+                user: entry.user.clone(),
+                disabled: !entry.enabled,
+                ..CronJob::default()
+            }));
+            crontab.set_job_timezone(crontab.job_count() - 1, &entry.timezone);
+        }
+        crontab.update_schedule();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const CONTENT: &str = "\
+MAILTO=ops@example.com
+# Nightly backup
+0 3 * * * /usr/bin/backup
+CRON_TZ=Asia/Tokyo
+#DISABLED# 30 1 * * 0 /usr/bin/rotate
+";
+
+    fn settings() -> CronSettings {
+        CronSettings {
+            timezone: "UTC".to_string(),
+            ..CronSettings::default()
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let crontab = Crontab::parse(CONTENT, false, &settings());
+        let file = CrontabFile::from_crontab(&crontab);
+        assert_eq!(
+            file.env,
+            vec![EnvEntry {
+                name: "MAILTO".to_string(),
+                value: "ops@example.com".to_string(),
+            }]
+        );
+        assert_eq!(
+            file.jobs[1],
+            JobEntry {
+                schedule: "30 1 * * 0".to_string(),
+                command: "/usr/bin/rotate".to_string(),
+                description: String::new(),
+                enabled: false,
+                user: String::new(),
+                timezone: "Asia/Tokyo".to_string(),
+            }
+        );
+
+        for format in [FileFormat::Json, FileFormat::Yaml] {
+            let content = file.to_string(format).unwrap();
+            assert_eq!(CrontabFile::from_str(&content, format).unwrap(), file);
+            let plan = ImportPlan::new(&crontab, &file, false, &settings()).unwrap();
+            assert!(plan.is_empty());
+        }
+
+        let minimal = "jobs:\n  - schedule: \"*/5 * * * *\"\n    command: /usr/bin/ping\n";
+        let minimal = CrontabFile::from_str(minimal, FileFormat::Yaml).unwrap();
+        assert_eq!(minimal.jobs[0].enabled, true);
+        assert_eq!(minimal.env, vec![]);
+        assert_eq!(
+            FileFormat::from_path(Path::new("jobs.yml")),
+            Ok(FileFormat::Yaml)
+        );
+        assert!(FileFormat::from_path(Path::new("jobs.txt")).is_err());
+    }
+
+    #[test]
+    fn test_import_plan() {
+        let mut crontab = Crontab::parse(CONTENT, false, &settings());
+        let content = r#"{
+            "env": [{ "name": "MAILTO", "value": "" }, { "name": "PATH", "value": "/usr/bin:/bin" }],
+            "jobs": [
+                {
+                    "schedule": "0 4 * * *",
+                    "command": "/usr/bin/backup",
+                    "description": "Nightly backup",
+                    "timezone": "Europe/Prague"
+                },
+                { "schedule": "*/5 * * * *", "command": "/usr/bin/ping", "enabled": false }
+            ]
+        }"#;
+        let file = CrontabFile::from_str(content, FileFormat::Json).unwrap();
+
+        let plan = ImportPlan::new(&crontab, &file, false, &settings()).unwrap();
+        assert_eq!(plan.added[0].command, "/usr/bin/ping");
+        assert_eq!(plan.changed[0].0, 0);
+        assert_eq!(plan.removed, vec![1]);
+        assert_eq!(
+            plan.summary(&crontab),
+            vec![
+                "+ */5 * * * * /usr/bin/ping",
+                "~ 0 3 * * * /usr/bin/backup  →  0 4 * * * /usr/bin/backup",
+                "- 30 1 * * 0 /usr/bin/rotate",
+                "~ Environment: MAILTO, PATH",
+            ]
+        );
+
+        plan.apply(&mut crontab);
+        assert_eq!(
+            crontab.render(),
+            "\
+MAILTO=
+PATH=/usr/bin:/bin
+CRON_TZ=Europe/Prague
+# Nightly backup
+0 4 * * * /usr/bin/backup
+
+CRON_TZ=UTC
+#DISABLED# */5 * * * * /usr/bin/ping
+"
+        );
+        assert!(ImportPlan::new(&crontab, &file, false, &settings())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_rejected_import() {
+        let crontab = Crontab::parse(CONTENT, false, &settings());
+        let content = r#"
+env:
+  - name: "BAD=NAME"
+    value: x
+  - name: PATH
+    value: /usr/bin
+jobs:
+  - schedule: "0 3 * * *"
+    command: /usr/bin/backup
+  - schedule: "61 * * * *"
+    command: /usr/bin/ping
+  - schedule: "0 * * * *"
+    command: /usr/bin/ping
+    timezone: Mars/Olympus
+  - schedule: "0 * * * *"
+    command: "date +%F"
+  - schedule: "0 * * * *"
+    command: "true\n0 * * * * /tmp/evil"
+  - schedule: "*/10 * * * * *"
+    command: /usr/bin/ping
+"#;
+        let file = CrontabFile::from_str(content, FileFormat::Yaml).unwrap();
+        let error = ImportPlan::new(&crontab, &file, false, &settings()).unwrap_err();
+        let lines: Vec<&str> = error.lines().collect();
+        assert_eq!(lines[0], "Nothing was imported:");
+        assert!(lines[1].starts_with("Variable 1 (BAD=NAME): "));
+        assert!(lines[2].starts_with("Job 2 (61 * * * * /usr/bin/ping): "));
+        assert_eq!(
+            lines[3],
+            "Job 3 (0 * * * * /usr/bin/ping): Unknown timezone Mars/Olympus"
+        );
+        assert!(lines[4].starts_with("Job 4 (0 * * * * date +%F): An unescaped %"));
+        assert!(lines[5].starts_with("Job 5 "));
+        assert!(lines[5].ends_with("A crontab entry can't span lines"));
+        assert!(lines[6].starts_with("Job 6 (*/10 * * * * * /usr/bin/ping): "));
+        assert_eq!(lines.len(), 7);
+
+        let seconds = CronSettings {
+            seconds: true,
+            ..settings()
+        };
+        let error = ImportPlan::new(&crontab, &file, false, &seconds).unwrap_err();
+        assert!(!error.contains("Job 6"));
+    }
+}
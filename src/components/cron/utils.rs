@@ -1,33 +1,275 @@
-use crate::components::cron::CronJob;
-use chrono::Utc;
+use crate::components::cron::{
+    backup::Backups,
+    crontab::{fingerprint, Crontab},
+    source::CrontabSource,
+};
+use crate::config::CronSettings;
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
 use chrono_tz::Tz;
 use cron_descriptor::cronparser::cron_expression_descriptor;
 use cron_descriptor::cronparser::Options;
-use cron_parser::parse;
-use std::io::{self, BufRead, Write};
+use cron_parser::{parse, parse_field};
+use std::fmt;
+use std::io;
 use std::panic;
-use std::process::{Command, Stdio};
 use std::str::FromStr;
-use unicode_width::UnicodeWidthStr;
 
-pub fn get_next_execution(cron_expr: &str, timezone: &str) -> String {
-    let timezone: Tz = if !timezone.is_empty() {
-        match Tz::from_str(timezone) {
-            Ok(tz) => tz,
-            Err(_) => return "Invalid timezone".to_string(),
+/// Cron macros and the five-field expressions they stand for. `@reboot` has no schedule.
+const CRON_MACROS: [(&str, Option<&str>); 8] = [
+    ("@reboot", None),
+    ("@yearly", Some("0 0 1 1 *")),
+    ("@annually", Some("0 0 1 1 *")),
+    ("@monthly", Some("0 0 1 * *")),
+    ("@weekly", Some("0 0 * * 0")),
+    ("@daily", Some("0 0 * * *")),
+    ("@midnight", Some("0 0 * * *")),
+    ("@hourly", Some("0 * * * *")),
+];
+
+/// Ranges of the minute, hour, day-of-month, month and day-of-week fields. Day of week accepts
+/// both 0 and 7 for Sunday.
+const FIELD_RANGES: [(u32, u32); 5] = [(0, 59), (0, 23), (1, 31), (1, 12), (0, 7)];
+
+const NEVER_FIRES: &str = "Never fires";
+
+pub fn is_cron_macro(cron_expr: &str) -> bool {
+    CRON_MACROS
+        .iter()
+        .any(|(name, _)| *name == cron_expr.trim())
+}
+
+pub fn is_reboot(cron_expr: &str) -> bool {
+    cron_expr.trim() == "@reboot"
+}
+
+/// Whether `field` reads as a day-of-week field, which tells a job line with a seconds field
+/// from a five-field one whose command starts there.
+pub fn is_day_of_week_field(field: &str) -> bool {
+    let (min, max) = FIELD_RANGES[4];
+    parse_field(field, min, max).is_ok_and(|days| !days.is_empty())
+}
+
+/// Checks a cron expression: five fields, a macro, or six fields (with seconds) when allowed.
+pub fn validate_cron_expression(cron_expr: &str, allow_seconds: bool) -> Result<(), String> {
+    let trimmed_expr = cron_expr.trim();
+    let fields = trimmed_expr.split_whitespace().count();
+
+    if trimmed_expr.starts_with('@') {
+        return if is_cron_macro(trimmed_expr) {
+            Ok(())
+        } else {
+            Err("Unknown cron macro".to_string())
+        };
+    }
+    if fields == 6 && !allow_seconds {
+        return Err("Seconds field is not enabled".to_string());
+    }
+    if fields != 5 && fields != 6 {
+        return Err("Invalid cron expression".to_string());
+    }
+
+    match next_run(trimmed_expr, &Utc::now()) {
+        Ok(_) => Ok(()),
+        Err(e) if e == NEVER_FIRES => Err(e),
+        Err(_) => Err("Invalid cron expression".to_string()),
+    }
+}
+
+/// Returns the first time after `after` at which the expression fires, or `None` for `@reboot`.
+///
+/// Accepts five-field expressions, macros and six-field expressions whose first field is seconds.
+pub fn next_run<Z: TimeZone>(
+    cron_expr: &str,
+    after: &DateTime<Z>,
+) -> Result<Option<DateTime<Z>>, String> {
+    let trimmed_expr = cron_expr.trim();
+
+    if trimmed_expr.starts_with('@') {
+        return match CRON_MACROS.iter().find(|(name, _)| *name == trimmed_expr) {
+            Some((_, Some(expression))) => next_minute_run(expression, after).map(Some),
+            Some((_, None)) => Ok(None),
+            None => Err("Unknown cron macro".to_string()),
+        };
+    }
+
+    let fields: Vec<&str> = trimmed_expr.split_whitespace().collect();
+    if fields.len() != 6 {
+        return next_minute_run(trimmed_expr, after).map(Some);
+    }
+
+    let seconds = parse_field(fields[0], 0, 59).map_err(|e| e.to_string())?;
+    let first_second = *seconds.first().ok_or("Invalid seconds field")?;
+    let expression = fields[1..].join(" ");
+
+    // The current minute may still have a matching second left.
+    let minute_start = after.clone()
+        - Duration::seconds(i64::from(after.second()))
+        - Duration::nanoseconds(i64::from(after.nanosecond()));
+    let previous_minute = minute_start.clone() - Duration::minutes(1);
+    if next_minute_run(&expression, &previous_minute)? == minute_start {
+        if let Some(second) = seconds.iter().find(|second| **second > after.second()) {
+            return Ok(Some(minute_start + Duration::seconds(i64::from(*second))));
         }
-    } else {
-        chrono_tz::Tz::UTC
+    }
+
+    next_minute_run(&expression, after)
+        .map(|next| Some(next + Duration::seconds(i64::from(first_second))))
+}
+
+/// Returns the next `count` times after `after` at which the expression fires.
+pub fn next_runs<Z: TimeZone>(
+    cron_expr: &str,
+    after: &DateTime<Z>,
+    count: usize,
+) -> Result<Vec<DateTime<Z>>, String> {
+    let mut runs: Vec<DateTime<Z>> = Vec::with_capacity(count);
+    while runs.len() < count {
+        let previous = runs.last().unwrap_or(after);
+        match next_run(cron_expr, previous)? {
+            Some(next) => runs.push(next),
+            None => break,
+        }
+    }
+    Ok(runs)
+}
+
+/// Finds the next matching minute of a five-field expression.
+///
+/// Days are matched the way cron does it: when both day of month and day of week are
+/// restricted (neither starts with `*`), a day matching either one is enough.
+fn next_minute_run<Z: TimeZone>(
+    cron_expr: &str,
+    after: &DateTime<Z>,
+) -> Result<DateTime<Z>, String> {
+    let fields: Vec<&str> = cron_expr.split_whitespace().collect();
+    if fields.len() != 5 {
+        return Err("Invalid cron expression".to_string());
+    }
+    let sets = fields
+        .iter()
+        .zip(FIELD_RANGES)
+        .map(|(field, (min, max))| parse_field(field, min, max).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, String>>()?;
+    let (days_of_month, days_of_week) = (&sets[2], &sets[4]);
+    let either_day = !fields[2].starts_with('*') && !fields[4].starts_with('*');
+
+    // Let the parser find matching times on any day, and check the day ourselves.
+    let any_day = format!("{} {} * {} *", fields[0], fields[1], fields[3]);
+    let mut current = after.clone();
+    loop {
+        let next = parse(&any_day, &current).map_err(|e| match e {
+            cron_parser::ParseError::InvalidCron => NEVER_FIRES.to_string(),
+            e => e.to_string(),
+        })?;
+        if next.year() - after.year() > 4 {
+            return Err(NEVER_FIRES.to_string());
+        }
+
+        let weekday = next.weekday().num_days_from_sunday();
+        let dom_matches = days_of_month.contains(&next.day());
+        let dow_matches =
+            days_of_week.contains(&weekday) || (weekday == 0 && days_of_week.contains(&7));
+        let matches = if either_day {
+            dom_matches || dow_matches
+        } else {
+            dom_matches && dow_matches
+        };
+        if matches {
+            return Ok(next);
+        }
+
+        // Skip the rest of the day.
+        current = next
+            .clone()
+            .with_hour(23)
+            .and_then(|end| end.with_minute(59))
+            .unwrap_or(next);
+    }
+}
+
+/// Warnings about expressions that are valid but probably not what was meant.
+pub fn schedule_warnings(cron_expr: &str) -> Vec<String> {
+    let trimmed_expr = cron_expr.trim();
+    if trimmed_expr.starts_with('@') {
+        return Vec::new();
+    }
+    let mut fields: Vec<&str> = trimmed_expr.split_whitespace().collect();
+    let mut warnings = Vec::new();
+
+    if fields.len() == 6 {
+        if fields[0] == "*" {
+            warnings.push("Runs every second of every matching minute".to_string());
+        }
+        fields.remove(0);
+    }
+    if fields.len() != 5 {
+        return warnings;
+    }
+
+    if fields[0] == "*" && !fields[1].starts_with('*') {
+        warnings.push(format!(
+            "Runs every minute while the hour matches; use \"0 {} …\" to run once",
+            fields[1]
+        ));
+    }
+    if !fields[2].starts_with('*') && !fields[4].starts_with('*') {
+        warnings.push(
+            "Runs when either the day of month or the day of week matches, not both".to_string(),
+        );
+    }
+    warnings
+}
+
+pub fn get_next_execution(cron_expr: &str, timezone: &str) -> String {
+    let Ok(timezone) = parse_timezone(timezone) else {
+        return "Invalid timezone".to_string();
     };
 
     let now = Utc::now().with_timezone(&timezone);
 
-    match parse(cron_expr, &now) {
-        Ok(next) => format!("{}", next),
+    match next_run(cron_expr, &now) {
+        Ok(Some(next)) => format_execution(&next),
+        Ok(None) => "At boot".to_string(),
+        Err(_) => "Invalid cron expression".to_string(),
+    }
+}
+
+/// Like [`get_next_execution`] for a job that runs in `job_timezone` (set with `CRON_TZ`). When
+/// that is not the local `timezone`, the local time of the run is added in parentheses.
+pub fn get_job_next_execution(cron_expr: &str, job_timezone: &str, timezone: &str) -> String {
+    if job_timezone.is_empty() || job_timezone == timezone {
+        return get_next_execution(cron_expr, timezone);
+    }
+    let (Ok(job_timezone), Ok(timezone)) = (parse_timezone(job_timezone), parse_timezone(timezone))
+    else {
+        return "Invalid timezone".to_string();
+    };
+
+    let now = Utc::now().with_timezone(&job_timezone);
+
+    match next_run(cron_expr, &now) {
+        Ok(Some(next)) => format!(
+            "{} ({})",
+            format_execution(&next),
+            next.with_timezone(&timezone).format("%Y-%m-%d %H:%M %Z")
+        ),
+        Ok(None) => "At boot".to_string(),
         Err(_) => "Invalid cron expression".to_string(),
     }
 }
 
+/// Parses an IANA zone name; an empty name means UTC.
+pub fn parse_timezone(name: &str) -> Result<Tz, String> {
+    if name.trim().is_empty() {
+        return Ok(Tz::UTC);
+    }
+    Tz::from_str(name.trim()).map_err(|_| format!("Unknown timezone {}", name.trim()))
+}
+
+fn format_execution(next: &DateTime<Tz>) -> String {
+    next.format("%Y-%m-%d %H:%M:%S %Z").to_string()
+}
+
 pub fn get_human_readable_cron(cron_expr: &str) -> Result<String, String> {
     let now = Utc::now();
     let trimmed_expr = cron_expr.trim();
@@ -36,13 +278,22 @@ pub fn get_human_readable_cron(cron_expr: &str) -> Result<String, String> {
         return Err("Cron expression is empty".to_string());
     }
 
-    if parse(cron_expr.trim(), &now).is_err() || cron_expr.contains(',') {
+    if is_reboot(trimmed_expr) {
+        return Ok("At system boot".to_string());
+    }
+    let expression = CRON_MACROS
+        .iter()
+        .find(|(name, _)| *name == trimmed_expr)
+        .and_then(|(_, expression)| *expression)
+        .unwrap_or(trimmed_expr);
+
+    if next_run(expression, &now).is_err() || expression.contains(',') {
         return Err("Unable to generate human-readable format".to_string());
     }
 
     let result = panic::catch_unwind(|| {
         cron_expression_descriptor::get_description_cron_options(
-            trimmed_expr,
+            expression,
             &Options::twenty_four_hour(),
         )
     });
@@ -54,117 +305,240 @@ pub fn get_human_readable_cron(cron_expr: &str) -> Result<String, String> {
     }
 }
 
-pub fn from_crontab(timezone: &str) -> Result<Vec<CronJob>, io::Error> {
-    let output = Command::new("crontab")
-        .arg("-l")
-        .stdout(Stdio::piped())
-        .output()?;
+pub fn from_crontab(source: &CrontabSource, settings: &CronSettings) -> Result<Crontab, io::Error> {
+    let content = source.read()?.unwrap_or_default();
+    Ok(Crontab::parse(&content, source.is_system(), settings))
+}
 
-    if !output.status.success() {
-        let stderr_output = String::from_utf8_lossy(&output.stderr);
+#[derive(Debug)]
+pub enum SaveError {
+    /// The crontab changed on disk since it was loaded; holds its current content.
+    Conflict(String),
+    Io(io::Error),
+}
 
-        if stderr_output.contains("no crontab for") {
-            return Ok(vec![CronJob::new({
-                CronJob {
-                    cron_notation: "User has no crontab".to_string(),
-                    job: String::new(),
-                    job_description: String::new(),
-                    next_execution: String::new(),
-                }
-            })]);
-        }
+impl From<io::Error> for SaveError {
+    fn from(err: io::Error) -> Self {
+        SaveError::Io(err)
+    }
+}
 
-        return Err(io::Error::other("Failed to read crontab"));
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Conflict(_) => write!(f, "the crontab was changed by someone else"),
+            SaveError::Io(err) => err.fmt(f),
+        }
     }
+}
 
-    let reader = io::BufReader::new(&output.stdout[..]);
-    let mut cron_jobs = Vec::new();
-    let mut comment: Option<String> = None;
+/// Writes the crontab back to its source after backing up what is there now. Nothing is written
+/// when nothing changed, and nothing is overwritten when the source changed since the crontab was
+/// loaded.
+pub fn save_to_crontab(
+    source: &CrontabSource,
+    crontab: &mut Crontab,
+    backups: &Backups,
+) -> Result<(), SaveError> {
+    if !crontab.is_modified() {
+        return Ok(());
+    }
+    let current = source.read()?.unwrap_or_default();
+    if fingerprint(&current) != crontab.fingerprint() {
+        return Err(SaveError::Conflict(current));
+    }
+    write_crontab(source, crontab, backups, &current)?;
+    Ok(())
+}
 
-    for line in reader.lines() {
-        let line = line?;
-        let line = line.trim();
+/// Writes the crontab back to its source regardless of what is there now.
+pub fn overwrite_crontab(
+    source: &CrontabSource,
+    crontab: &mut Crontab,
+    backups: &Backups,
+) -> io::Result<()> {
+    let current = source.read()?.unwrap_or_default();
+    write_crontab(source, crontab, backups, &current)
+}
 
-        if line.is_empty() {
-            continue;
-        }
+fn write_crontab(
+    source: &CrontabSource,
+    crontab: &mut Crontab,
+    backups: &Backups,
+    current: &str,
+) -> io::Result<()> {
+    backups.create(current)?;
+    source.write(&crontab.render())?;
+    crontab.mark_saved();
+    Ok(())
+}
 
-        if line.starts_with('#') {
-            comment = Some(line.trim_start_matches('#').trim().to_string());
-        } else {
-            let parts: Vec<&str> = line.split_whitespace().collect();
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
 
-            if parts.len() < 6 {
-                continue;
-            }
+    use super::*;
+    use crate::components::cron::CronJob;
 
-            let cron_notation = parts[..5].join(" ");
-            let job = parts[5..].join(" ");
-            let modified_next_execution = get_next_execution(&cron_notation, timezone);
+    #[test]
+    fn test_next_run_macros() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 14, 10, 30, 0).unwrap();
+        assert_eq!(
+            next_run("@daily", &now).unwrap(),
+            Some(Utc.with_ymd_and_hms(2025, 3, 15, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            next_run("@hourly", &now).unwrap(),
+            Some(Utc.with_ymd_and_hms(2025, 3, 14, 11, 0, 0).unwrap())
+        );
+        assert_eq!(next_run("@reboot", &now).unwrap(), None);
+        assert!(next_run("@often", &now).is_err());
+    }
 
-            cron_jobs.push(CronJob {
-                cron_notation,
-                job,
-                job_description: comment.take().unwrap_or_default(),
-                next_execution: modified_next_execution,
-            });
-        }
+    #[test]
+    fn test_next_run_with_seconds() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 14, 10, 30, 20).unwrap();
+        assert_eq!(
+            next_run("*/15 * * * * *", &now).unwrap(),
+            Some(Utc.with_ymd_and_hms(2025, 3, 14, 10, 30, 30).unwrap())
+        );
+        assert_eq!(
+            next_run("10 0 * * * *", &now).unwrap(),
+            Some(Utc.with_ymd_and_hms(2025, 3, 14, 11, 0, 10).unwrap())
+        );
     }
 
-    Ok(cron_jobs)
-}
+    #[test]
+    fn test_day_of_month_or_day_of_week() {
+        // 2025-03-14 is a Friday.
+        let now = Utc.with_ymd_and_hms(2025, 3, 14, 10, 30, 0).unwrap();
+        let runs = next_runs("0 0 13 * 5", &now, 5).unwrap();
+        assert_eq!(
+            runs,
+            vec![
+                Utc.with_ymd_and_hms(2025, 3, 21, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 3, 28, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 4, 4, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 4, 11, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 4, 13, 0, 0, 0).unwrap(),
+            ]
+        );
+        // A step in day of week still restricts, but starts with `*` so both must match.
+        assert_eq!(
+            next_run("0 0 1-7 * */7", &now).unwrap(),
+            Some(Utc.with_ymd_and_hms(2025, 4, 6, 0, 0, 0).unwrap())
+        );
+    }
 
-pub fn save_to_crontab(cron_jobs: &[CronJob]) -> io::Result<()> {
-    let mut new_crontab = String::new();
+    #[test]
+    fn test_day_of_week() {
+        let sunday = Utc.with_ymd_and_hms(2025, 3, 16, 4, 30, 0).unwrap();
+        assert_eq!(
+            next_run("0,30 4 * * 1", &sunday).unwrap(),
+            Some(Utc.with_ymd_and_hms(2025, 3, 17, 4, 0, 0).unwrap())
+        );
+        assert_eq!(
+            next_run("0 0 * * 7", &sunday).unwrap(),
+            Some(Utc.with_ymd_and_hms(2025, 3, 23, 0, 0, 0).unwrap())
+        );
+    }
 
-    for job in cron_jobs {
-        if !job.job.is_empty() {
-            if !new_crontab.is_empty() {
-                new_crontab.push('\n');
-            }
-            if !job.job_description.is_empty() {
-                new_crontab.push_str(&format!("# {}\n", job.job_description));
-            }
-            new_crontab.push_str(&format!("{} {}\n", job.cron_notation, job.job));
-        }
+    #[test]
+    fn test_never_fires() {
+        assert_eq!(
+            validate_cron_expression("0 0 31 2 *", false),
+            Err("Never fires".to_string())
+        );
+        assert_eq!(
+            validate_cron_expression("0 0 30 2 *", false),
+            Err("Never fires".to_string())
+        );
+        assert!(validate_cron_expression("0 0 29 2 *", false).is_ok());
     }
 
-    let mut process = Command::new("crontab").stdin(Stdio::piped()).spawn()?;
+    #[test]
+    fn test_schedule_warnings() {
+        assert_eq!(schedule_warnings("0 3 * * *"), Vec::<String>::new());
+        assert_eq!(schedule_warnings("* 3 * * *").len(), 1);
+        assert_eq!(schedule_warnings("0 0 13 * 5").len(), 1);
+        assert_eq!(schedule_warnings("* 0 * * * *").len(), 1);
+        assert_eq!(schedule_warnings("@daily"), Vec::<String>::new());
+    }
 
-    if let Some(stdin) = process.stdin.as_mut() {
-        stdin.write_all(new_crontab.as_bytes())?;
+    #[test]
+    fn test_validate_cron_expression() {
+        assert!(validate_cron_expression("*/5 * * * *", false).is_ok());
+        assert!(validate_cron_expression("@weekly", false).is_ok());
+        assert!(validate_cron_expression("@reboot", false).is_ok());
+        assert!(validate_cron_expression("@sometimes", false).is_err());
+        assert!(validate_cron_expression("0 */5 * * * *", false).is_err());
+        assert!(validate_cron_expression("0 */5 * * * *", true).is_ok());
+        assert!(validate_cron_expression("* * *", true).is_err());
     }
 
-    process.wait()?;
-    Ok(())
-}
+    #[test]
+    fn test_save_detects_concurrent_changes() {
+        let path = std::env::temp_dir().join(format!("tuixel-conflict-{}", std::process::id()));
+        std::fs::write(&path, "0 3 * * * root /usr/bin/backup\n").unwrap();
+        let source = CrontabSource::System(path.clone());
+        let settings = CronSettings::default();
+        let backups = Backups::new(path.with_extension("backups"), 5);
 
-pub fn constraint_len_calculator(items: &[CronJob]) -> (u16, u16, u16) {
-    let cron_notation_len = items
-        .iter()
-        .map(|job| job.cron_notation.as_str())
-        .map(UnicodeWidthStr::width)
-        .max()
-        .unwrap_or(0);
+        let mut crontab = from_crontab(&source, &settings).unwrap();
+        crontab.remove_job(0);
+        std::fs::write(&path, "0 4 * * * root /usr/bin/backup\n").unwrap();
+        match save_to_crontab(&source, &mut crontab, &backups) {
+            Err(SaveError::Conflict(theirs)) => {
+                assert_eq!(theirs, "0 4 * * * root /usr/bin/backup\n")
+            }
+            other => panic!("expected a conflict, got {:?}", other),
+        }
 
-    let next_execution_len = items
-        .iter()
-        .map(|job| job.next_execution.as_str())
-        .map(UnicodeWidthStr::width)
-        .max()
-        .unwrap_or(0);
+        overwrite_crontab(&source, &mut crontab, &backups).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+        assert_eq!(
+            backups.list().unwrap()[0].read().unwrap(),
+            "0 4 * * * root /usr/bin/backup\n"
+        );
+        crontab.push_job(CronJob {
+            cron_notation: "@daily".to_string(),
+            job: "/usr/bin/true".to_string(),
+            user: "root".to_string(),
+            ..CronJob::default()
+        });
+        save_to_crontab(&source, &mut crontab, &backups).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_dir_all(backups.dir()).unwrap();
+    }
 
-    let job_description_len = items
-        .iter()
-        .map(|job| job.job_description.as_str())
-        .map(UnicodeWidthStr::width)
-        .max()
-        .unwrap_or(0);
-
-    #[allow(clippy::cast_possible_truncation)]
-    (
-        cron_notation_len as u16,
-        next_execution_len as u16,
-        job_description_len as u16,
-    )
+    #[test]
+    fn test_job_next_execution() {
+        let next = get_job_next_execution("30 12 * * *", "Asia/Tokyo", "UTC");
+        assert_eq!(&next[10..24], " 12:30:00 JST ");
+        assert!(next.ends_with(" 03:30 UTC)"), "{}", next);
+
+        let next = get_job_next_execution("30 12 * * *", "", "Asia/Tokyo");
+        assert!(next.ends_with(" 12:30:00 JST"), "{}", next);
+        assert_eq!(
+            get_job_next_execution("@reboot", "Asia/Tokyo", "UTC"),
+            "At boot"
+        );
+        assert_eq!(
+            get_job_next_execution("30 12 * * *", "Mars/Olympus", "UTC"),
+            "Invalid timezone"
+        );
+    }
+
+    #[test]
+    fn test_human_readable_macros() {
+        assert_eq!(
+            get_human_readable_cron("@reboot").unwrap(),
+            "At system boot"
+        );
+        assert_eq!(
+            get_human_readable_cron("@daily").unwrap(),
+            get_human_readable_cron("0 0 * * *").unwrap()
+        );
+    }
 }
@@ -0,0 +1,138 @@
+use chrono::NaiveDateTime;
+
+use super::CronJob;
+
+/// The order the cron table lists its jobs in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// As they appear in the crontab.
+    #[default]
+    Crontab,
+    NextExecution,
+    Notation,
+    Description,
+}
+
+impl SortOrder {
+    pub fn next(self) -> Self {
+        match self {
+            SortOrder::Crontab => SortOrder::NextExecution,
+            SortOrder::NextExecution => SortOrder::Notation,
+            SortOrder::Notation => SortOrder::Description,
+            SortOrder::Description => SortOrder::Crontab,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortOrder::Crontab => "crontab order",
+            SortOrder::NextExecution => "next execution",
+            SortOrder::Notation => "cron notation",
+            SortOrder::Description => "description",
+        }
+    }
+}
+
+/// Whether the job's command, description or notation contains `query`, ignoring case.
+pub fn matches(job: &CronJob, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    query.is_empty()
+        || [&job.job, &job.job_description, &job.cron_notation]
+            .iter()
+            .any(|field| field.to_lowercase().contains(&query))
+}
+
+/// Indices of the jobs that match `query`, in the given order.
+pub fn visible_jobs(jobs: &[&CronJob], query: &str, order: SortOrder) -> Vec<usize> {
+    let mut rows: Vec<usize> = (0..jobs.len())
+        .filter(|&i| matches(jobs[i], query))
+        .collect();
+    match order {
+        SortOrder::Crontab => {}
+        // Jobs without an upcoming run (disabled, @reboot, invalid) go last.
+        SortOrder::NextExecution => rows.sort_by_key(|&i| {
+            let next = next_execution(jobs[i]);
+            (next.is_none(), next)
+        }),
+        SortOrder::Notation => rows.sort_by_key(|&i| jobs[i].cron_notation.clone()),
+        SortOrder::Description => rows.sort_by_key(|&i| jobs[i].job_description.to_lowercase()),
+    }
+    rows
+}
+
+/// `next_execution` is shown as e.g. "2025-03-01 04:00:00 CET".
+fn next_execution(job: &CronJob) -> Option<NaiveDateTime> {
+    let datetime = job.next_execution.get(..19)?;
+    NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M:%S").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn job(notation: &str, command: &str, description: &str, next: &str) -> CronJob {
+        CronJob {
+            cron_notation: notation.to_string(),
+            job: command.to_string(),
+            job_description: description.to_string(),
+            next_execution: next.to_string(),
+            ..CronJob::default()
+        }
+    }
+
+    #[test]
+    fn test_visible_jobs() {
+        let jobs = [
+            job(
+                "0 3 * * *",
+                "/usr/bin/backup",
+                "Nightly backup",
+                "2025-03-02 03:00:00 UTC",
+            ),
+            job("@reboot", "/usr/bin/mount-all", "", "At boot"),
+            job(
+                "*/5 * * * *",
+                "/usr/bin/ping",
+                "Health check",
+                "2025-03-01 12:05:00 UTC",
+            ),
+            job("30 1 * * 0", "/usr/bin/rotate", "rotate logs", "Disabled"),
+        ];
+        let jobs: Vec<&CronJob> = jobs.iter().collect();
+
+        assert_eq!(
+            visible_jobs(&jobs, "", SortOrder::Crontab),
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(visible_jobs(&jobs, "BACKUP", SortOrder::Crontab), vec![0]);
+        assert_eq!(visible_jobs(&jobs, "check", SortOrder::Crontab), vec![2]);
+        assert_eq!(visible_jobs(&jobs, "*/5", SortOrder::Crontab), vec![2]);
+        assert_eq!(
+            visible_jobs(&jobs, "/usr/bin/r", SortOrder::Crontab),
+            vec![3]
+        );
+        assert_eq!(
+            visible_jobs(&jobs, "nothing", SortOrder::Crontab),
+            Vec::<usize>::new()
+        );
+
+        assert_eq!(
+            visible_jobs(&jobs, "", SortOrder::NextExecution),
+            vec![2, 0, 1, 3]
+        );
+        assert_eq!(
+            visible_jobs(&jobs, "", SortOrder::Notation),
+            vec![2, 0, 3, 1]
+        );
+        assert_eq!(
+            visible_jobs(&jobs, "", SortOrder::Description),
+            vec![1, 2, 0, 3]
+        );
+        assert_eq!(
+            visible_jobs(&jobs, "usr", SortOrder::NextExecution),
+            vec![2, 0, 1, 3]
+        );
+    }
+}
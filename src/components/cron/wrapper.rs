@@ -0,0 +1,346 @@
+use std::fs;
+use std::path::Path;
+
+use crate::config::CronSettings;
+
+/// Options tuixel wraps around the command of a job: a lock against overlapping runs, a
+/// time limit and a log of the output.
+///
+/// A wrapped command reads `flock -n LOCK timeout DURATION sh -c 'COMMAND' >> LOG 2>&1`,
+/// with the parts of the options that are off left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Wrapper {
+    /// Lock file `flock -n` holds while the job runs; a run that finds it held is skipped.
+    pub lock: Option<String>,
+    /// How long the job may run before `timeout` stops it, e.g. "30m".
+    pub timeout: Option<String>,
+    /// File the output of the job is appended to.
+    pub log: Option<String>,
+}
+
+impl Wrapper {
+    /// The wrapper with the given options on, for a job running `command`.
+    ///
+    /// The lock and log files of `current` are kept, so that editing a job does not move
+    /// them; new ones are named after the command, in the directories of `settings`.
+    pub fn configure(
+        current: &Wrapper,
+        lock: bool,
+        timeout: &str,
+        log: bool,
+        command: &str,
+        settings: &CronSettings,
+    ) -> Self {
+        let path = |dir: &str, extension: &str| {
+            format!(
+                "{}/{}.{}",
+                dir.trim_end_matches('/'),
+                job_name(command),
+                extension
+            )
+        };
+        Self {
+            lock: lock.then(|| {
+                current
+                    .lock
+                    .clone()
+                    .unwrap_or_else(|| path(&settings.lock_dir, "lock"))
+            }),
+            timeout: Some(timeout.trim().to_string()).filter(|timeout| !timeout.is_empty()),
+            log: log.then(|| {
+                current
+                    .log
+                    .clone()
+                    .unwrap_or_else(|| path(&settings.log_dir, "log"))
+            }),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock.is_none() && self.timeout.is_none() && self.log.is_none()
+    }
+
+    /// The command as it goes into the crontab.
+    pub fn wrap(&self, command: &str) -> String {
+        if self.is_empty() {
+            return command.to_string();
+        }
+        let mut words = Vec::new();
+        if let Some(lock) = &self.lock {
+            words.push(format!("flock -n {}", quote(lock)));
+        }
+        if let Some(timeout) = &self.timeout {
+            words.push(format!("timeout {}", quote(timeout)));
+        }
+        // The shell keeps the options around the whole command, `&&` and pipes included.
+        words.push(format!("sh -c {}", quote(command)));
+        if let Some(log) = &self.log {
+            words.push(format!(">> {} 2>&1", quote(log)));
+        }
+        words.join(" ")
+    }
+
+    /// Splits a command from the crontab into the command the job runs and its wrapper.
+    ///
+    /// Only wrappers [`Wrapper::wrap`] makes are recognized; any other command comes back as
+    /// it is, with no options.
+    pub fn unwrap(command: &str) -> (String, Self) {
+        let unwrapped = shell_words(command).and_then(|words| {
+            let mut wrapper = Self::default();
+            let mut rest = words.as_slice();
+            if let [flock, n, lock, tail @ ..] = rest {
+                if flock == "flock" && n == "-n" {
+                    wrapper.lock = Some(lock.clone());
+                    rest = tail;
+                }
+            }
+            if let [timeout, duration, tail @ ..] = rest {
+                if timeout == "timeout" {
+                    wrapper.timeout = Some(duration.clone());
+                    rest = tail;
+                }
+            }
+            let inner = match rest {
+                [sh, c, inner] if sh == "sh" && c == "-c" => inner,
+                [sh, c, inner, append, log, redirect]
+                    if sh == "sh" && c == "-c" && append == ">>" && redirect == "2>&1" =>
+                {
+                    wrapper.log = Some(log.clone());
+                    inner
+                }
+                _ => return None,
+            };
+            Some((inner.clone(), wrapper))
+        });
+        match unwrapped {
+            // Written by hand in a slightly different way; left alone so a save keeps it.
+            Some((inner, wrapper)) if !wrapper.is_empty() && wrapper.wrap(&inner) == command => {
+                (inner, wrapper)
+            }
+            _ => (command.to_string(), Self::default()),
+        }
+    }
+
+    /// Short description for the table, e.g. "lock, 30m, log".
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.lock.is_some() {
+            parts.push("lock".to_string());
+        }
+        if let Some(timeout) = &self.timeout {
+            parts.push(timeout.clone());
+        }
+        if self.log.is_some() {
+            parts.push("log".to_string());
+        }
+        parts.join(", ")
+    }
+}
+
+/// Checks a duration the way `timeout` reads it: a number with an optional `s`, `m`, `h` or
+/// `d` suffix.
+pub fn validate_timeout(timeout: &str) -> Result<(), String> {
+    let timeout = timeout.trim();
+    if timeout.is_empty() {
+        return Ok(());
+    }
+    let number = timeout.trim_end_matches(['s', 'm', 'h', 'd']);
+    let valid = timeout.len() - number.len() <= 1
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && number.matches('.').count() <= 1
+        && number.parse::<f64>().is_ok_and(|number| number > 0.0);
+    if valid {
+        Ok(())
+    } else {
+        Err("e.g. 90s, 30m, 2h".to_string())
+    }
+}
+
+/// Creates the directory of a lock or log file, and refuses one that everyone can write to:
+/// another user could plant a symlink there and have the job, often run as root, clobber
+/// whatever it points to.
+pub fn prepare_dir(path: &str) -> Result<(), String> {
+    let Some(dir) = Path::new(path)
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+    else {
+        return Ok(());
+    };
+    let error = |e: std::io::Error| format!("{}: {}", dir.display(), e);
+    fs::create_dir_all(dir).map_err(error)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(dir).map_err(error)?.permissions().mode();
+        if mode & 0o002 != 0 {
+            return Err(format!("{} is writable by everyone", dir.display()));
+        }
+    }
+    Ok(())
+}
+
+/// Refuses new lock and log files for a job cron runs as `owner` when that is not `user`, who
+/// runs tuixel: the files go to directories of `user`, and a job that can't open them would
+/// silently stop running.
+pub fn check_owner(owner: &str, user: &str) -> Result<(), String> {
+    if owner.is_empty() || owner == user {
+        Ok(())
+    } else {
+        Err(format!("runs as {}, who can't use {}'s files", owner, user))
+    }
+}
+
+/// A file name for the lock and log of a job, e.g. "usr-local-bin-backup-sh" for
+/// `/usr/local/bin/backup.sh`.
+fn job_name(command: &str) -> String {
+    let name: Vec<String> = command
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let mut name = name.join("-");
+    name.truncate(40);
+    let name = name.trim_end_matches('-');
+    if name.is_empty() {
+        "job".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Quotes `word` for the shell, unless it is safe as it is.
+fn quote(word: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "/._-+:=@,".contains(c);
+    if !word.is_empty() && word.chars().all(safe) {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+/// Splits a command into words the way the shell would, for the quoting [`quote`] does.
+/// `None` when a quote is left open.
+fn shell_words(text: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' => word.get_or_insert_with(String::new).push(chars.next()?),
+            c if c.is_whitespace() => words.extend(word.take()),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Some(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_wrap_and_unwrap() {
+        let settings = CronSettings {
+            lock_dir: "/run/lock/".to_string(),
+            log_dir: "/var/log/cron".to_string(),
+            ..CronSettings::default()
+        };
+        let command = "cd /srv/app && ./backup.sh --full";
+        let wrapper =
+            Wrapper::configure(&Wrapper::default(), true, "30m", true, command, &settings);
+        let wrapped = wrapper.wrap(command);
+        assert_eq!(
+            wrapped,
+            "flock -n /run/lock/cd-srv-app-backup-sh-full.lock timeout 30m \
+             sh -c 'cd /srv/app && ./backup.sh --full' >> /var/log/cron/cd-srv-app-backup-sh-full.log 2>&1"
+        );
+        assert_eq!(
+            Wrapper::unwrap(&wrapped),
+            (command.to_string(), wrapper.clone())
+        );
+        assert_eq!(wrapper.summary(), "lock, 30m, log");
+
+        let quoted = "echo 'it''s' \\%";
+        let timeout_only = Wrapper {
+            timeout: Some("2h".to_string()),
+            ..Wrapper::default()
+        };
+        assert_eq!(
+            Wrapper::unwrap(&timeout_only.wrap(quoted)),
+            (quoted.to_string(), timeout_only)
+        );
+
+        // Editing keeps the files, turning an option off drops it.
+        let edited = Wrapper::configure(&wrapper, true, "", false, "other", &settings);
+        assert_eq!(edited.lock, wrapper.lock);
+        assert_eq!(edited.timeout, None);
+        assert_eq!(edited.log, None);
+    }
+
+    #[test]
+    fn test_unwrap_leaves_other_commands() {
+        for command in [
+            "/usr/bin/backup",
+            "sh -c 'echo hi'",
+            "flock -n /tmp/x.lock /usr/bin/backup",
+            "flock  -n /tmp/x.lock sh -c true",
+            "timeout 5m sh -c 'unterminated",
+            "sh -c true >> /tmp/log",
+        ] {
+            assert_eq!(
+                Wrapper::unwrap(command),
+                (command.to_string(), Wrapper::default())
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_owner() {
+        assert_eq!(check_owner("root", "root"), Ok(()));
+        assert_eq!(check_owner("", "root"), Ok(()));
+        assert_eq!(
+            check_owner("alice", "root"),
+            Err("runs as alice, who can't use root's files".to_string())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_prepare_dir() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("tuixel-wrapper-{}", std::process::id()));
+        let locks = dir.join("locks");
+        let lock = locks.join("job.lock").to_string_lossy().to_string();
+        assert_eq!(prepare_dir(&lock), Ok(()));
+        assert!(locks.is_dir());
+
+        fs::set_permissions(&locks, fs::Permissions::from_mode(0o1777)).unwrap();
+        assert!(prepare_dir(&lock).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_validate_timeout() {
+        for timeout in ["", "90", "90s", "1.5h", "2d"] {
+            assert_eq!(validate_timeout(timeout), Ok(()));
+        }
+        for timeout in ["soon", "5ms", "0", "1.2.3m", "-5m", "m"] {
+            assert!(validate_timeout(timeout).is_err(), "{}", timeout);
+        }
+    }
+}
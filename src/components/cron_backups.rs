@@ -0,0 +1,228 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Text},
+    widgets::{Block, BorderType, Borders, Clear, List, ListState, Paragraph},
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{
+    cron::{
+        backup::{Backup, Backups},
+        diff::diff_to_lines,
+        source::CrontabSource,
+    },
+    Component,
+};
+use crate::{
+    action::{Action, Module},
+    config::Config,
+    draw::{center, Drawable},
+    style::{EditWindowStyles, TableStyles},
+};
+
+/// Lists the backups of the open crontab and restores one of them.
+#[derive(Default)]
+pub struct CronBackups {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    enabled: bool,
+    styles: EditWindowStyles,
+    table_styles: TableStyles,
+    source: CrontabSource,
+    backups: Vec<(Backup, String)>,
+    current: String,
+    error: Option<String>,
+    confirming: bool,
+    state: ListState,
+}
+
+impl Drawable for CronBackups {}
+
+impl CronBackups {
+    pub fn new() -> Self {
+        Self {
+            styles: EditWindowStyles::new(),
+            table_styles: TableStyles::new(),
+            ..Self::default()
+        }
+    }
+
+    fn store(&self) -> Backups {
+        Backups::for_source(&self.source, self.config.settings.cron.backups)
+    }
+
+    fn load(&mut self) {
+        self.error = None;
+        self.confirming = false;
+        self.state = ListState::default().with_selected(Some(0));
+        self.current = match self.source.read() {
+            Ok(content) => content.unwrap_or_default(),
+            Err(e) => {
+                self.error = Some(e.to_string());
+                String::new()
+            }
+        };
+        self.backups = match self.store().list() {
+            Ok(backups) => backups
+                .into_iter()
+                .filter_map(|backup| {
+                    let content = backup.read().ok()?;
+                    Some((backup, content))
+                })
+                .collect(),
+            Err(e) => {
+                self.error = Some(e.to_string());
+                Vec::new()
+            }
+        };
+    }
+
+    fn selected(&self) -> Option<&(Backup, String)> {
+        self.state
+            .selected()
+            .and_then(|index| self.backups.get(index))
+    }
+
+    fn block(&self, title: String) -> Block<'static> {
+        Block::default()
+            .style(self.styles.window_style)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(self.styles.window_border_style)
+            .title(title)
+    }
+
+    fn draw_list(&mut self, frame: &mut Frame, area: Rect) {
+        let items: Vec<Text> = self
+            .backups
+            .iter()
+            .map(|(backup, _)| {
+                Text::from(format!(" {}", backup.created.format("%Y-%m-%d %H:%M:%S")))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(self.block(" Backups ".to_string()))
+            .style(self.styles.title_style)
+            .highlight_style(self.table_styles.selected_row_style);
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+
+    fn draw_diff(&self, frame: &mut Frame, area: Rect) {
+        let (title, lines) = match (&self.error, self.selected()) {
+            (Some(e), _) => (" Error ".to_string(), vec![Line::raw(e.clone())]),
+            (None, None) => (
+                " Diff ".to_string(),
+                vec![Line::raw(format!(
+                    "No backups yet. They are kept in {}",
+                    self.store().dir().display()
+                ))],
+            ),
+            (None, Some((_, content))) => (
+                " Current crontab (-) and this backup (+) ".to_string(),
+                diff_to_lines(&self.current, content),
+            ),
+        };
+        let diff = Paragraph::new(Text::from(lines))
+            .style(self.styles.title_style)
+            .block(self.block(title));
+        frame.render_widget(diff, area);
+    }
+
+    /// Hands the backup to the table, which saves it as any other change.
+    fn restore(&mut self) -> Option<Action> {
+        let (_, content) = self.selected()?;
+        Some(Action::RestoreBackup(content.clone()))
+    }
+}
+
+impl Component for CronBackups {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::SwitchCrontabSource(ref source) = action {
+            self.source = source.clone();
+        }
+        if let Action::ChangeMode(Module::CronBackups) = action {
+            self.load();
+            self.enabled = true;
+        }
+        if self.enabled {
+            match action {
+                Action::ChangeMode(Module::Cron) => {
+                    self.enabled = false;
+                }
+                Action::Select if self.selected().is_some() => {
+                    self.confirming = true;
+                }
+                Action::Confirm if self.confirming => {
+                    return Ok(self.restore());
+                }
+                Action::MoveUp => {
+                    self.confirming = false;
+                    self.state.select_previous();
+                }
+                Action::MoveDown => {
+                    self.confirming = false;
+                    self.state.select_next();
+                }
+                Action::MoveToTheFirst => {
+                    self.confirming = false;
+                    self.state.select_first();
+                }
+                Action::MoveToTheLast => {
+                    self.confirming = false;
+                    self.state.select_last();
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, _area: Rect) -> Result<()> {
+        if self.enabled {
+            let area = center(
+                frame.area(),
+                Constraint::Percentage(80),
+                Constraint::Percentage(80),
+            );
+            frame.render_widget(Clear, area);
+
+            let [main_area, footer_area] =
+                Layout::vertical([Constraint::Min(3), Constraint::Length(2)])
+                    .flex(Flex::SpaceBetween)
+                    .areas(area);
+            let [list_area, diff_area] =
+                Layout::horizontal([Constraint::Length(24), Constraint::Min(10)]).areas(main_area);
+
+            self.draw_list(frame, list_area);
+            self.draw_diff(frame, diff_area);
+
+            let keybinds = if self.confirming {
+                vec![
+                    ("Restore the selected backup?", ""),
+                    ("<y>", "Yes, restore"),
+                    ("<↓↑>", "No"),
+                ]
+            } else {
+                vec![
+                    ("<Esc>", "Close"),
+                    ("<↓↑>", "Move up and down"),
+                    ("<Enter>", "Restore"),
+                ]
+            };
+            self.draw_footer(frame, footer_area, keybinds)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{cron::diff::diff_to_lines, Component};
+use crate::{
+    action::{Action, Module},
+    config::Config,
+    draw::{center, Drawable},
+    style::EditWindowStyles,
+};
+
+/// Shown when a save finds that the crontab was changed by someone else since it was loaded.
+#[derive(Default)]
+pub struct CronConflict {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    enabled: bool,
+    styles: EditWindowStyles,
+    ours: String,
+    theirs: String,
+    show_diff: bool,
+    scroll: u16,
+}
+
+impl Drawable for CronConflict {}
+
+impl CronConflict {
+    pub fn new() -> Self {
+        Self {
+            styles: EditWindowStyles::new(),
+            ..Self::default()
+        }
+    }
+
+    fn block(&self, title: &str) -> Block<'static> {
+        Block::default()
+            .style(self.styles.window_style)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(self.styles.invalid_input_style)
+            .title(title.to_string())
+    }
+}
+
+impl Component for CronConflict {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::PassConflict(ref ours, ref theirs) = action {
+            self.ours = ours.clone();
+            self.theirs = theirs.clone();
+            self.show_diff = false;
+            self.scroll = 0;
+        }
+        if let Action::ChangeMode(Module::CronConflict) = action {
+            self.enabled = true;
+        }
+        if self.enabled {
+            match action {
+                Action::ChangeMode(Module::Cron) => {
+                    self.enabled = false;
+                }
+                // `Cron` does the write; the dialog only has to close.
+                Action::OverwriteCrontab => {
+                    self.enabled = false;
+                    return Ok(Some(Action::ChangeMode(Module::Cron)));
+                }
+                Action::ToggleDiff => {
+                    self.show_diff = !self.show_diff;
+                    self.scroll = 0;
+                }
+                Action::MoveUp => {
+                    self.scroll = self.scroll.saturating_sub(1);
+                }
+                Action::MoveDown => {
+                    self.scroll = self.scroll.saturating_add(1);
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, _area: Rect) -> Result<()> {
+        if self.enabled {
+            let height = if self.show_diff {
+                Constraint::Percentage(80)
+            } else {
+                Constraint::Length(9)
+            };
+            let area = center(frame.area(), Constraint::Percentage(70), height);
+            frame.render_widget(Clear, area);
+
+            let [main_area, footer_area] =
+                Layout::vertical([Constraint::Min(3), Constraint::Length(2)])
+                    .flex(Flex::SpaceBetween)
+                    .areas(area);
+
+            let content = if self.show_diff {
+                let lines = diff_to_lines(&self.theirs, &self.ours);
+                Paragraph::new(Text::from(lines))
+                    .block(self.block(" Their version (-) and yours (+) "))
+                    .scroll((self.scroll, 0))
+            } else {
+                Paragraph::new(Text::from(vec![
+                    Line::raw(""),
+                    Line::raw("The crontab was changed by someone else after it was loaded."),
+                    Line::raw(""),
+                    Line::raw(
+                        "Reload to drop your change and see theirs, or overwrite to replace their \
+                         version with yours.",
+                    ),
+                ]))
+                .centered()
+                .wrap(Wrap { trim: true })
+                .block(self.block(" Conflict "))
+            };
+            frame.render_widget(content.style(self.styles.title_style), main_area);
+
+            let mut keybinds = vec![
+                ("<r>", "Reload"),
+                ("<o>", "Overwrite"),
+                ("<d>", "Toggle diff"),
+            ];
+            if self.show_diff {
+                keybinds.push(("<↓↑>", "Scroll"));
+            }
+            self.draw_footer(frame, footer_area, keybinds)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use color_eyre::Result;
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Constraint, Flex, Layout, Rect},
+    prelude::Frame,
+    style::Style,
+    widgets::{Block, BorderType, Borders, Clear},
+};
+use tokio::sync::mpsc::UnboundedSender;
+use tui_textarea::TextArea;
+
+use super::Component;
+use crate::{
+    action::{Action, Module},
+    config::Config,
+    draw::{center, Drawable},
+    style::EditWindowStyles,
+};
+
+pub struct CronEnv {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    enabled: bool,
+    styles: EditWindowStyles,
+    editor: TextArea<'static>,
+}
+
+impl Drawable for CronEnv {}
+
+impl Default for CronEnv {
+    fn default() -> Self {
+        Self {
+            command_tx: None,
+            config: Config::default(),
+            enabled: false,
+            styles: EditWindowStyles::new(),
+            editor: TextArea::default(),
+        }
+    }
+}
+
+impl CronEnv {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn initial_render(&mut self, vars: &[(String, String)]) {
+        let lines: Vec<String> = vars
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        self.editor = TextArea::new(lines);
+        self.editor
+            .set_placeholder_text("One NAME=value per line, e.g. MAILTO=ops@example.com");
+        self.editor.set_cursor_line_style(Style::default());
+        self.editor.set_cursor_style(self.styles.cursor_style);
+    }
+}
+
+/// Parses `NAME=value` lines, skipping empty ones.
+pub fn parse_env_lines(lines: &[String]) -> Result<Vec<(String, String)>, String> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let (name, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected NAME=value", i + 1))?;
+            let name = name.trim();
+            let valid = name
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return Err(format!("line {}: invalid variable name", i + 1));
+            }
+            Ok((name.to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+impl Component for CronEnv {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.enabled {
+            match key.code {
+                KeyCode::Esc => {}
+                KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {}
+                _ => {
+                    self.editor.input(key);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::PassEnvVars(ref vars) = action {
+            if !self.enabled {
+                self.initial_render(vars);
+            }
+        }
+        if let Action::ChangeMode(Module::CronEnv) = action {
+            self.enabled = true;
+        }
+        if self.enabled {
+            match action {
+                Action::ChangeMode(Module::Cron) => {
+                    self.enabled = false;
+                }
+                Action::Confirm => {
+                    if let Ok(vars) = parse_env_lines(self.editor.lines()) {
+                        let tx = self.command_tx.clone().unwrap();
+                        tx.send(Action::PassEnvVars(vars))?;
+                        self.enabled = false;
+                        return Ok(Some(Action::ChangeMode(Module::Cron)));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, _area: Rect) -> Result<()> {
+        if self.enabled {
+            let area = center(
+                frame.area(),
+                Constraint::Percentage(60),
+                Constraint::Length(18),
+            );
+            frame.render_widget(Clear, area);
+
+            let [editor_area, footer_area] =
+                Layout::vertical([Constraint::Length(16), Constraint::Length(2)])
+                    .flex(Flex::SpaceBetween)
+                    .areas(area);
+
+            let (title, border_style) = match parse_env_lines(self.editor.lines()) {
+                Ok(_) => (
+                    " Environment variables ".to_string(),
+                    self.styles.window_border_style,
+                ),
+                Err(message) => (
+                    format!(" Environment variables ({}) ", message),
+                    self.styles.invalid_input_style,
+                ),
+            };
+            self.editor.set_block(
+                Block::default()
+                    .style(self.styles.window_style)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick)
+                    .border_style(border_style)
+                    .title(title),
+            );
+            frame.render_widget(&self.editor, editor_area);
+
+            self.draw_footer(
+                frame,
+                footer_area,
+                vec![
+                    ("<Esc>", "Close without saving"),
+                    ("<Ctrl-s>", "Save and close"),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+}
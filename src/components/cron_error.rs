@@ -0,0 +1,104 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::Component;
+use crate::{
+    action::{Action, Module},
+    config::Config,
+    draw::{center, Drawable},
+    style::EditWindowStyles,
+};
+
+/// Tells what failed on the crontab, and what came of it.
+#[derive(Default)]
+pub struct CronError {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    enabled: bool,
+    styles: EditWindowStyles,
+    message: String,
+    note: String,
+}
+
+impl Drawable for CronError {}
+
+impl CronError {
+    pub fn new() -> Self {
+        Self {
+            styles: EditWindowStyles::new(),
+            ..Self::default()
+        }
+    }
+}
+
+impl Component for CronError {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::PassError(ref message, ref note) = action {
+            self.message = message.clone();
+            self.note = note.clone();
+        }
+        if let Action::ChangeMode(Module::CronError) = action {
+            self.enabled = true;
+        }
+        if self.enabled {
+            if let Action::ChangeMode(Module::Cron) = action {
+                self.enabled = false;
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, _area: Rect) -> Result<()> {
+        if self.enabled {
+            let area = center(
+                frame.area(),
+                Constraint::Percentage(60),
+                Constraint::Length(10),
+            );
+            frame.render_widget(Clear, area);
+
+            let [main_area, footer_area] =
+                Layout::vertical([Constraint::Min(3), Constraint::Length(2)])
+                    .flex(Flex::SpaceBetween)
+                    .areas(area);
+
+            let content = Paragraph::new(Text::from(vec![
+                Line::raw(""),
+                Line::raw(self.message.clone()),
+                Line::raw(""),
+                Line::raw(self.note.clone()),
+            ]))
+            .centered()
+            .wrap(Wrap { trim: true })
+            .style(self.styles.title_style)
+            .block(
+                Block::default()
+                    .style(self.styles.window_style)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick)
+                    .border_style(self.styles.invalid_input_style)
+                    .title(" Error "),
+            );
+            frame.render_widget(content, main_area);
+
+            self.draw_footer(frame, footer_area, vec![("<Esc>", "Close")])?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListState, Paragraph},
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{
+    cron::history::{read_history, HistoryEntry, HistoryKind, HISTORY_LIMIT},
+    Component,
+};
+use crate::{
+    action::{Action, Module},
+    config::Config,
+    draw::{center, Drawable},
+    style::{EditWindowStyles, TableStyles},
+};
+
+#[derive(Default)]
+pub struct CronHistory {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    enabled: bool,
+    styles: EditWindowStyles,
+    table_styles: TableStyles,
+    command: String,
+    history: Option<Result<Vec<HistoryEntry>, String>>,
+    state: ListState,
+}
+
+impl Drawable for CronHistory {}
+
+impl CronHistory {
+    pub fn new() -> Self {
+        Self {
+            styles: EditWindowStyles::new(),
+            table_styles: TableStyles::new(),
+            ..Self::default()
+        }
+    }
+
+    fn load(&mut self, command: &str) {
+        self.command = command.to_string();
+        self.history = None;
+        self.state = ListState::default().with_selected(Some(0));
+
+        // journalctl can take a while on large journals, keep it off the render loop.
+        let tx = self.command_tx.clone().unwrap();
+        let command = command.to_string();
+        tokio::task::spawn_blocking(move || {
+            let history = read_history(&command, HISTORY_LIMIT);
+            let _ = tx.send(Action::HistoryLoaded(command, history));
+        });
+    }
+
+    fn entry_line(entry: &HistoryEntry) -> Line<'static> {
+        let (label, color) = match entry.kind {
+            HistoryKind::Run => ("RUN  ", Color::LightGreen),
+            HistoryKind::Mail => ("MAIL ", Color::LightYellow),
+            HistoryKind::Error => ("ERROR", Color::LightRed),
+        };
+        Line::from(vec![
+            Span::raw(format!(" {}  ", entry.timestamp)),
+            Span::styled(label, Style::default().fg(color)),
+            Span::raw(format!("  ({}) {}", entry.user, entry.message)),
+        ])
+    }
+
+    fn draw_history(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .style(self.styles.window_style)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(self.styles.window_border_style)
+            .title(format!(" History: {} ", self.command));
+
+        let message = match &self.history {
+            None => Some("Reading cron logs…".to_string()),
+            Some(Err(e)) => Some(e.clone()),
+            Some(Ok(entries)) if entries.is_empty() => {
+                Some("No runs of this job found in the cron logs".to_string())
+            }
+            Some(Ok(_)) => None,
+        };
+        if let Some(message) = message {
+            frame.render_widget(
+                Paragraph::new(format!(" {}", message))
+                    .style(self.styles.title_style)
+                    .block(block),
+                area,
+            );
+            return;
+        }
+
+        let items: Vec<Line> = self
+            .history
+            .iter()
+            .flatten()
+            .flatten()
+            .map(Self::entry_line)
+            .collect();
+        let list = List::new(items)
+            .block(block)
+            .style(self.styles.title_style)
+            .highlight_style(self.table_styles.selected_row_style);
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
+
+impl Component for CronHistory {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ShowHistory(ref command) => {
+                self.load(command);
+            }
+            // Ignore results of a lookup for a job that is no longer shown.
+            Action::HistoryLoaded(ref command, ref history) if *command == self.command => {
+                self.history = Some(history.clone());
+            }
+            Action::ChangeMode(Module::CronHistory) => {
+                self.enabled = true;
+            }
+            _ => {}
+        }
+        if self.enabled {
+            match action {
+                Action::ChangeMode(Module::Cron) => {
+                    self.enabled = false;
+                }
+                Action::MoveUp => {
+                    self.state.select_previous();
+                }
+                Action::MoveDown => {
+                    self.state.select_next();
+                }
+                Action::MoveToTheFirst => {
+                    self.state.select_first();
+                }
+                Action::MoveToTheLast => {
+                    self.state.select_last();
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, _area: Rect) -> Result<()> {
+        if self.enabled {
+            let area = center(
+                frame.area(),
+                Constraint::Percentage(80),
+                Constraint::Percentage(70),
+            );
+            frame.render_widget(Clear, area);
+
+            let [history_area, footer_area] =
+                Layout::vertical([Constraint::Min(3), Constraint::Length(2)])
+                    .flex(Flex::SpaceBetween)
+                    .areas(area);
+
+            self.draw_history(frame, history_area);
+            self.draw_footer(
+                frame,
+                footer_area,
+                vec![("<Esc>", "Close"), ("<↓↑>", "Move up and down")],
+            )?;
+        }
+        Ok(())
+    }
+}
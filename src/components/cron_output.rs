@@ -0,0 +1,223 @@
+use std::time::{Duration, Instant};
+
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    prelude::Frame,
+    style::{Color, Style},
+    text::Line,
+    widgets::{
+        Block, BorderType, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation,
+        ScrollbarState,
+    },
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::Component;
+use crate::{
+    action::{Action, Module},
+    config::Config,
+    draw::{center, Drawable},
+    style::{EditWindowStyles, TableStyles},
+};
+
+pub struct CronOutput {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    enabled: bool,
+    styles: EditWindowStyles,
+    table_styles: TableStyles,
+    command: String,
+    lines: Vec<Line<'static>>,
+    started: Instant,
+    finished: Option<(Option<i32>, Duration)>,
+    scroll: usize,
+    follow: bool,
+    page_height: usize,
+}
+
+impl Drawable for CronOutput {}
+
+impl Default for CronOutput {
+    fn default() -> Self {
+        Self {
+            command_tx: None,
+            config: Config::default(),
+            enabled: false,
+            styles: EditWindowStyles::new(),
+            table_styles: TableStyles::new(),
+            command: String::new(),
+            lines: vec![],
+            started: Instant::now(),
+            finished: None,
+            scroll: 0,
+            follow: true,
+            page_height: 0,
+        }
+    }
+}
+
+impl CronOutput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(self.page_height)
+    }
+
+    fn status(&self) -> (String, Style) {
+        match self.finished {
+            None => (
+                format!(" Running… {:.1}s ", self.started.elapsed().as_secs_f64()),
+                self.styles.title_style,
+            ),
+            Some((code, duration)) => {
+                let seconds = duration.as_secs_f64();
+                match code {
+                    Some(0) => (
+                        format!(" Exit code 0 in {:.2}s ", seconds),
+                        self.styles.valid_input_style,
+                    ),
+                    Some(code) => (
+                        format!(" Exit code {} in {:.2}s ", code, seconds),
+                        self.styles.invalid_input_style,
+                    ),
+                    None => (
+                        format!(" Terminated by a signal after {:.2}s ", seconds),
+                        self.styles.invalid_input_style,
+                    ),
+                }
+            }
+        }
+    }
+}
+
+impl Component for CronOutput {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::JobStarted(ref command) => {
+                self.command = command.clone();
+                self.lines.clear();
+                self.started = Instant::now();
+                self.finished = None;
+                self.scroll = 0;
+                self.follow = true;
+            }
+            Action::JobStdout(ref line) => {
+                self.lines.push(Line::raw(line.clone()));
+            }
+            Action::JobStderr(ref line) => {
+                self.lines.push(Line::styled(
+                    line.clone(),
+                    Style::default().fg(Color::LightRed),
+                ));
+            }
+            Action::JobFinished(code, duration) => {
+                self.finished = Some((code, duration));
+            }
+            Action::ChangeMode(Module::CronOutput) => {
+                self.enabled = true;
+            }
+            _ => {}
+        }
+        if self.enabled {
+            match action {
+                Action::ChangeMode(Module::Cron) => {
+                    self.enabled = false;
+                }
+                Action::MoveUp => {
+                    self.follow = false;
+                    self.scroll = self.scroll.min(self.max_scroll()).saturating_sub(1);
+                }
+                Action::MoveDown => {
+                    self.scroll = (self.scroll + 1).min(self.max_scroll());
+                    self.follow = self.scroll == self.max_scroll();
+                }
+                Action::MoveToTheFirst => {
+                    self.follow = false;
+                    self.scroll = 0;
+                }
+                Action::MoveToTheLast => {
+                    self.follow = true;
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, _area: Rect) -> Result<()> {
+        if self.enabled {
+            let area = center(
+                frame.area(),
+                Constraint::Percentage(80),
+                Constraint::Percentage(80),
+            );
+            frame.render_widget(Clear, area);
+
+            let [output_area, footer_area] =
+                Layout::vertical([Constraint::Min(3), Constraint::Length(2)])
+                    .flex(Flex::SpaceBetween)
+                    .areas(area);
+
+            self.page_height = output_area.height.saturating_sub(2) as usize;
+            if self.follow {
+                self.scroll = self.max_scroll();
+            }
+            self.scroll = self.scroll.min(self.max_scroll());
+
+            let (status, status_style) = self.status();
+            let block = Block::default()
+                .style(self.styles.window_style)
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick)
+                .border_style(self.styles.window_border_style)
+                .title(Line::styled(
+                    format!(" $ {} ", self.command),
+                    self.styles.title_style,
+                ))
+                .title_bottom(Line::styled(status, status_style).right_aligned());
+            let output = Paragraph::new(self.lines.clone())
+                .style(self.styles.title_style)
+                .block(block)
+                .scroll((self.scroll as u16, 0));
+            frame.render_widget(output, output_area);
+
+            let mut scroll_state = ScrollbarState::new(self.max_scroll()).position(self.scroll);
+            frame.render_stateful_widget(
+                Scrollbar::default()
+                    .orientation(ScrollbarOrientation::VerticalRight)
+                    .style(self.table_styles.scrollbar_style)
+                    .begin_symbol(None)
+                    .end_symbol(None),
+                output_area.inner(Margin {
+                    vertical: 1,
+                    horizontal: 0,
+                }),
+                &mut scroll_state,
+            );
+
+            self.draw_footer(
+                frame,
+                footer_area,
+                vec![
+                    ("<Esc>", "Close and stop the job"),
+                    ("<↓↑>", "Scroll"),
+                    ("<End>", "Follow output"),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+}
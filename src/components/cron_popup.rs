@@ -1,3 +1,7 @@
+use std::str::FromStr;
+
+use chrono::Utc;
+use chrono_tz::Tz;
 use color_eyre::Result;
 use ratatui::{
     crossterm::event::{KeyCode, KeyEvent},
@@ -5,25 +9,41 @@ use ratatui::{
     prelude::Frame,
     style::{Color, Style},
     text::{Line, Span, Text},
-    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
 };
 use tokio::sync::mpsc::UnboundedSender;
 use tui_textarea::TextArea;
 
 use super::Component;
-use crate::components::cron::utils::get_human_readable_cron;
+use crate::components::cron::utils::{
+    get_human_readable_cron, is_reboot, next_runs, parse_timezone, schedule_warnings,
+    validate_cron_expression,
+};
+use crate::components::cron::{
+    ai::ask_for_cron,
+    natural::natural_to_cron,
+    source::current_username,
+    wrapper::{check_owner, prepare_dir, validate_timeout, Wrapper},
+};
 use crate::{
     action::{Action, Module},
     config::Config,
-    draw::Drawable,
+    draw::{center, Drawable},
     style::EditWindowStyles,
 };
 
+/// How many upcoming runs the preview lists.
+const PREVIEW_RUNS: usize = 10;
+
 #[derive(Default, PartialEq)]
 enum ActiveInput {
     #[default]
     CronNotation,
+    Timezone,
     Job,
+    Lock,
+    Timeout,
+    Log,
     JobDescription,
     AIQuestion,
 }
@@ -31,8 +51,12 @@ enum ActiveInput {
 impl ActiveInput {
     pub fn next(&self) -> Self {
         match self {
-            ActiveInput::CronNotation => ActiveInput::Job,
-            ActiveInput::Job => ActiveInput::JobDescription,
+            ActiveInput::CronNotation => ActiveInput::Timezone,
+            ActiveInput::Timezone => ActiveInput::Job,
+            ActiveInput::Job => ActiveInput::Lock,
+            ActiveInput::Lock => ActiveInput::Timeout,
+            ActiveInput::Timeout => ActiveInput::Log,
+            ActiveInput::Log => ActiveInput::JobDescription,
             ActiveInput::JobDescription => ActiveInput::AIQuestion,
             ActiveInput::AIQuestion => ActiveInput::CronNotation,
         }
@@ -47,14 +71,31 @@ pub struct CronPopup {
     styles: EditWindowStyles,
     index: i32,
     cron_notation: TextArea<'static>,
+    timezone: TextArea<'static>,
     job: TextArea<'static>,
+    timeout: TextArea<'static>,
     job_description: TextArea<'static>,
     ai_question: TextArea<'static>,
     current_input: ActiveInput,
     cron_notation_value: String,
+    timezone_value: String,
     job_value: String,
+    /// The wrapper the job had when the popup opened, whose files are kept.
+    wrapper: Wrapper,
+    /// The user cron runs the job as.
+    owner: String,
+    lock: bool,
+    timeout_value: String,
+    log: bool,
+    /// Why the directory of the lock or log file can't be used, found on confirm.
+    lock_error: Option<String>,
+    log_error: Option<String>,
     job_description_value: String,
     ai_question_value: String,
+    ai_answer: Option<Result<String, String>>,
+    ai_pending: Option<String>,
+    /// Lint findings for the job as it was when the popup opened.
+    lints: Vec<String>,
 }
 
 impl Drawable for CronPopup {}
@@ -68,14 +109,27 @@ impl Default for CronPopup {
             index: -1,
             styles: EditWindowStyles::new(),
             cron_notation: TextArea::default(),
+            timezone: TextArea::default(),
             job: TextArea::default(),
+            timeout: TextArea::default(),
             job_description: TextArea::default(),
             ai_question: TextArea::default(),
             current_input: ActiveInput::CronNotation,
             cron_notation_value: String::new(),
+            timezone_value: String::new(),
             job_value: String::new(),
+            wrapper: Wrapper::default(),
+            owner: String::new(),
+            lock: false,
+            timeout_value: String::new(),
+            log: false,
+            lock_error: None,
+            log_error: None,
             job_description_value: String::new(),
             ai_question_value: String::new(),
+            ai_answer: None,
+            ai_pending: None,
+            lints: Vec::new(),
             is_new: true,
         }
     }
@@ -89,8 +143,12 @@ impl CronPopup {
     fn flash_inputs(&mut self) {
         self.cron_notation.delete_line_by_head();
         self.cron_notation.delete_line_by_end();
+        self.timezone.delete_line_by_head();
+        self.timezone.delete_line_by_end();
         self.job.delete_line_by_head();
         self.job.delete_line_by_end();
+        self.timeout.delete_line_by_head();
+        self.timeout.delete_line_by_end();
         self.job_description.delete_line_by_head();
         self.job_description.delete_line_by_end();
         self.ai_question.delete_line_by_head();
@@ -99,9 +157,19 @@ impl CronPopup {
 
     fn flash_values(&mut self) {
         self.cron_notation_value.clear();
+        self.timezone_value.clear();
         self.job_value.clear();
+        self.wrapper = Wrapper::default();
+        self.lock = false;
+        self.timeout_value.clear();
+        self.log = false;
+        self.lock_error = None;
+        self.log_error = None;
         self.job_description_value.clear();
         self.ai_question_value.clear();
+        self.ai_answer = None;
+        self.ai_pending = None;
+        self.lints.clear();
     }
 
     fn initial_render(&mut self) {
@@ -121,9 +189,15 @@ impl CronPopup {
                 .border_style(self.styles.selected_input_border_style),
         );
 
+        self.timezone.set_placeholder_text("Default");
+        self.timezone.set_cursor_line_style(Style::default());
+
         job_input.set_placeholder_text("Enter a job");
         job_input.set_cursor_line_style(Style::default());
 
+        self.timeout.set_placeholder_text("None");
+        self.timeout.set_cursor_line_style(Style::default());
+
         description_input.set_placeholder_text("Enter a description");
         description_input.set_cursor_line_style(Style::default());
 
@@ -132,10 +206,217 @@ impl CronPopup {
         self.ai_question.set_cursor_line_style(Style::default());
     }
 
-    fn make_request_to_ai(&mut self) -> String {
-        let question = self.ai_question_value.clone();
-        let response = format!("0 0 1 1 *: {}:", question);
-        response
+    fn draw_preview(&self, frame: &mut Frame, area: Rect) {
+        let timezone = parse_timezone(self.timezone_value.trim())
+            .ok()
+            .filter(|_| !self.timezone_value.trim().is_empty())
+            .unwrap_or_else(|| {
+                Tz::from_str(&self.config.settings.cron.timezone).unwrap_or(Tz::UTC)
+            });
+        let expression = self.cron_notation_value.trim();
+        let warning_style = Style::default().fg(Color::LightYellow);
+
+        let mut lines: Vec<Line> = schedule_warnings(expression)
+            .into_iter()
+            .chain(self.lints.iter().cloned())
+            .map(|warning| Line::styled(format!("⚠ {}", warning), warning_style))
+            .collect();
+        if validate_cron_expression(expression, self.config.settings.cron.seconds).is_ok() {
+            let now = Utc::now().with_timezone(&timezone);
+            match next_runs(expression, &now, PREVIEW_RUNS) {
+                Ok(_) if is_reboot(expression) => {
+                    lines.push(Line::raw("Runs only at system boot"));
+                }
+                Ok(runs) => lines.extend(
+                    runs.iter()
+                        .map(|run| Line::raw(run.format("%a %Y-%m-%d %H:%M:%S").to_string())),
+                ),
+                Err(e) => lines.push(Line::styled(e, self.styles.invalid_input_style)),
+            }
+        }
+
+        let preview = Paragraph::new(Text::from(lines))
+            .style(self.styles.title_style)
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(self.styles.unselected_input_border_style)
+                    .title(format!("Next runs ({})", timezone)),
+            );
+        frame.render_widget(preview, area);
+    }
+
+    /// Fills the cron notation from the plain English question, or explains why it can't.
+    ///
+    /// With `settings.ai.url` set the question goes to the model in the background and the answer
+    /// arrives as `Action::AiAnswer`; otherwise the offline converter is used.
+    fn make_request_to_ai(&mut self) {
+        let question = self.ai_question_value.trim().to_string();
+        if self.config.settings.ai.url.is_empty() {
+            match natural_to_cron(&question) {
+                Ok(conversion) => {
+                    self.set_cron_notation(&conversion.expression);
+                    self.ai_answer = Some(Ok(conversion
+                        .note
+                        .unwrap_or_else(|| "converted".to_string())));
+                }
+                Err(message) => self.ai_answer = Some(Err(message)),
+            }
+            return;
+        }
+        if question.is_empty() || self.ai_pending.as_ref() == Some(&question) {
+            return;
+        }
+
+        let settings = self.config.settings.ai.clone();
+        let allow_seconds = self.config.settings.cron.seconds;
+        let tx = self.command_tx.clone().unwrap();
+        self.ai_pending = Some(question.clone());
+        self.ai_answer = Some(Ok(format!("asking {}…", settings.model)));
+        tokio::spawn(async move {
+            let answer = ask_for_cron(&settings, &question, allow_seconds).await;
+            let _ = tx.send(Action::AiAnswer(question, answer));
+        });
+    }
+
+    fn set_cron_notation(&mut self, expression: &str) {
+        self.cron_notation_value = expression.to_string();
+        self.cron_notation.delete_line_by_head();
+        self.cron_notation.delete_line_by_end();
+        self.cron_notation.insert_str(&self.cron_notation_value);
+    }
+
+    /// The wrapper for the options as they are set now.
+    fn wrapped_job(&self) -> Wrapper {
+        Wrapper::configure(
+            &self.wrapper,
+            self.lock,
+            &self.timeout_value,
+            self.log,
+            &self.job_value,
+            &self.config.settings.cron,
+        )
+    }
+
+    /// Prepares the directories of the lock and log files, moving to the option whose directory
+    /// can't be used.
+    ///
+    /// Files the job did not have yet are only made for jobs of the user running tuixel.
+    fn prepare_dirs(&mut self) -> bool {
+        let wrapper = self.wrapped_job();
+        let user = current_username();
+        let check = |path: &Option<String>, current: &Option<String>| {
+            let path = path.as_deref()?;
+            let owner = if current.as_deref() == Some(path) {
+                Ok(())
+            } else {
+                check_owner(&self.owner, &user)
+            };
+            owner.and_then(|()| prepare_dir(path)).err()
+        };
+        self.lock_error = check(&wrapper.lock, &self.wrapper.lock);
+        self.log_error = check(&wrapper.log, &self.wrapper.log);
+        if self.lock_error.is_some() {
+            self.current_input = ActiveInput::Lock;
+        } else if self.log_error.is_some() {
+            self.current_input = ActiveInput::Log;
+        }
+        self.lock_error.is_none() && self.log_error.is_none()
+    }
+
+    /// The lock, timeout and log options, each in its own box.
+    fn draw_options(&mut self, frame: &mut Frame, area: Rect) {
+        let [lock, timeout, log] = Layout::horizontal([
+            Constraint::Percentage(35),
+            Constraint::Percentage(30),
+            Constraint::Percentage(35),
+        ])
+        .spacing(1)
+        .areas(area);
+        let wrapper = self.wrapped_job();
+        let border = |input: ActiveInput| {
+            if self.current_input == input {
+                self.styles.selected_input_border_style
+            } else {
+                self.styles.unselected_input_border_style
+            }
+        };
+        let toggle = |path: Option<String>,
+                      title: &'static str,
+                      error: &Option<String>,
+                      border_style: Style| {
+            let text = match path {
+                Some(path) => format!("[x] {}", path),
+                None => "[ ] Off".to_string(),
+            };
+            let (title, border_style) = match error {
+                Some(error) => (
+                    format!("{} ({})", title, error),
+                    self.styles.invalid_input_style,
+                ),
+                None => (title.to_string(), border_style),
+            };
+            Paragraph::new(text).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(border_style)
+                    .title(title),
+            )
+        };
+        frame.render_widget(
+            toggle(
+                wrapper.lock,
+                "Prevent overlap (flock)",
+                &self.lock_error,
+                border(ActiveInput::Lock),
+            ),
+            lock,
+        );
+        frame.render_widget(
+            toggle(
+                wrapper.log,
+                "Log output",
+                &self.log_error,
+                border(ActiveInput::Log),
+            ),
+            log,
+        );
+
+        let selected = self.current_input == ActiveInput::Timeout;
+        let (title, border_style) = match validate_timeout(&self.timeout_value) {
+            Err(message) => (
+                format!("Timeout ({})", message),
+                self.styles.invalid_input_style,
+            ),
+            Ok(_) => ("Timeout".to_string(), border(ActiveInput::Timeout)),
+        };
+        self.timeout.set_cursor_style(if selected {
+            self.styles.cursor_style
+        } else {
+            Style::default()
+        });
+        self.timeout.set_block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style)
+                .title(title),
+        );
+        frame.render_widget(&self.timeout, timeout);
+    }
+
+    fn ai_title(&self) -> Line<'static> {
+        match &self.ai_answer {
+            None => Line::raw("Ask AI"),
+            Some(Ok(message)) => Line::from(vec![
+                Span::raw("Ask AI "),
+                Span::styled(format!("({})", message), self.styles.valid_input_style),
+            ]),
+            Some(Err(message)) => Line::from(vec![
+                Span::raw("Ask AI "),
+                Span::styled(format!("({})", message), self.styles.invalid_input_style),
+            ]),
+        }
     }
 }
 
@@ -157,10 +438,7 @@ impl Component for CronPopup {
                 KeyCode::Enter => {}
                 KeyCode::Up => {
                     if self.current_input == ActiveInput::AIQuestion {
-                        self.cron_notation_value = self.make_request_to_ai();
-                        self.cron_notation.delete_line_by_head();
-                        self.cron_notation.delete_line_by_end();
-                        self.cron_notation.insert_str(&self.cron_notation_value);
+                        self.make_request_to_ai();
                     }
                 }
                 _ => match self.current_input {
@@ -174,6 +452,14 @@ impl Component for CronPopup {
                             }
                         }
                     }
+                    ActiveInput::Timezone => {
+                        if self.timezone.input(key) {
+                            self.timezone_value.clear();
+                            if let Some(first_line) = self.timezone.lines().first() {
+                                self.timezone_value.push_str(first_line);
+                            }
+                        }
+                    }
                     ActiveInput::Job => {
                         let job_input = &mut self.job;
                         let job_value = &mut self.job_value;
@@ -184,6 +470,23 @@ impl Component for CronPopup {
                             }
                         }
                     }
+                    ActiveInput::Lock if key.code == KeyCode::Char(' ') => {
+                        self.lock = !self.lock;
+                        self.lock_error = None;
+                    }
+                    ActiveInput::Log if key.code == KeyCode::Char(' ') => {
+                        self.log = !self.log;
+                        self.log_error = None;
+                    }
+                    ActiveInput::Lock | ActiveInput::Log => {}
+                    ActiveInput::Timeout => {
+                        if self.timeout.input(key) {
+                            self.timeout_value.clear();
+                            if let Some(first_line) = self.timeout.lines().first() {
+                                self.timeout_value.push_str(first_line);
+                            }
+                        }
+                    }
                     ActiveInput::JobDescription => {
                         let job_description_input = &mut self.job_description;
                         let job_description_value = &mut self.job_description_value;
@@ -198,6 +501,8 @@ impl Component for CronPopup {
                         let ai_question_input = &mut self.ai_question;
                         let ai_question_value = &mut self.ai_question_value;
                         if ai_question_input.input(key) {
+                            self.ai_answer = None;
+                            self.ai_pending = None;
                             ai_question_value.clear();
                             if let Some(first_line) = ai_question_input.lines().first() {
                                 ai_question_value.push_str(first_line);
@@ -214,17 +519,29 @@ impl Component for CronPopup {
         if let Action::ChangeMode(Module::CronPopup) = action {
             self.enabled = true;
         }
+        if let Action::PassJobOwner(ref owner) = action {
+            self.owner = owner.clone();
+        }
         if let Action::PassData(ref cron) = action {
             self.initial_render();
             if !cron.is_empty() {
-                self.is_new = false;
                 self.index = cron[0].parse().unwrap();
+                self.is_new = self.index == -1;
                 self.cron_notation_value = cron[1].clone();
-                self.job_value = cron[2].clone();
+                let (job, wrapper) = Wrapper::unwrap(&cron[2]);
+                self.job_value = job;
+                self.lock = wrapper.lock.is_some();
+                self.timeout_value = wrapper.timeout.clone().unwrap_or_default();
+                self.log = wrapper.log.is_some();
+                self.wrapper = wrapper;
                 self.job_description_value = cron[3].clone();
+                self.timezone_value = cron.get(4).cloned().unwrap_or_default();
+                self.lints = cron.iter().skip(5).cloned().collect();
 
                 self.cron_notation.insert_str(&self.cron_notation_value);
+                self.timezone.insert_str(&self.timezone_value);
                 self.job.insert_str(&self.job_value);
+                self.timeout.insert_str(&self.timeout_value);
                 self.job_description.insert_str(&self.job_description_value);
             } else {
                 self.is_new = true;
@@ -232,6 +549,18 @@ impl Component for CronPopup {
         }
         if self.enabled {
             match action {
+                // Answers to questions that were edited or abandoned meanwhile are dropped.
+                Action::AiAnswer(ref question, ref answer)
+                    if self.ai_pending.as_ref() == Some(question) =>
+                {
+                    self.ai_pending = None;
+                    self.ai_answer = Some(answer.clone().and_then(|expression| {
+                        self.set_cron_notation(&expression);
+                        validate(&mut self.cron_notation, self.config.settings.cron.seconds)
+                            .map(|_| "answered by the model".to_string())
+                            .map_err(|ValidationError::InvalidCronExpression(message)| message)
+                    }));
+                }
                 Action::ChangeMode(Module::Cron) => {
                     self.flash_inputs();
                     self.flash_values();
@@ -240,23 +569,33 @@ impl Component for CronPopup {
                 Action::SwitchElement => {
                     self.current_input = self.current_input.next();
                 }
-                Action::Confirm => match validate(&mut self.cron_notation) {
-                    Ok(_) => {
-                        let tx = self.command_tx.clone().unwrap();
-                        tx.send(Action::PassData(vec![
-                            self.index.to_string(),
-                            self.cron_notation_value.clone(),
-                            self.job_value.clone(),
-                            self.job_description_value.clone(),
-                        ]))
-                        .unwrap();
-                        self.enabled = false;
-                        return Ok(Some(Action::ChangeMode(Module::Cron)));
-                    }
-                    Err(ValidationError::InvalidCronExpression(_)) => {
-                        self.current_input = ActiveInput::CronNotation;
+                Action::Confirm => {
+                    match validate(&mut self.cron_notation, self.config.settings.cron.seconds) {
+                        Ok(_) if parse_timezone(&self.timezone_value).is_err() => {
+                            self.current_input = ActiveInput::Timezone;
+                        }
+                        Ok(_) if validate_timeout(&self.timeout_value).is_err() => {
+                            self.current_input = ActiveInput::Timeout;
+                        }
+                        Ok(_) if !self.prepare_dirs() => {}
+                        Ok(_) => {
+                            let tx = self.command_tx.clone().unwrap();
+                            tx.send(Action::PassData(vec![
+                                self.index.to_string(),
+                                self.cron_notation_value.clone(),
+                                self.wrapped_job().wrap(&self.job_value),
+                                self.job_description_value.clone(),
+                                self.timezone_value.trim().to_string(),
+                            ]))
+                            .unwrap();
+                            self.enabled = false;
+                            return Ok(Some(Action::ChangeMode(Module::Cron)));
+                        }
+                        Err(ValidationError::InvalidCronExpression(_)) => {
+                            self.current_input = ActiveInput::CronNotation;
+                        }
                     }
-                },
+                }
                 _ => {}
             }
         }
@@ -268,11 +607,11 @@ impl Component for CronPopup {
             let area = center(
                 frame.area(),
                 Constraint::Percentage(70),
-                Constraint::Length(22),
+                Constraint::Length(27),
             );
             frame.render_widget(Clear, area);
 
-            let layout = Layout::vertical([Constraint::Length(20), Constraint::Length(2)])
+            let layout = Layout::vertical([Constraint::Length(25), Constraint::Length(2)])
                 .flex(Flex::SpaceBetween);
             let [main_area, footer_area] = layout.areas(area);
 
@@ -284,16 +623,28 @@ impl Component for CronPopup {
 
             frame.render_widget(main_block, main_area);
 
-            let main = Layout::vertical([
-                Constraint::Length(4),
+            let main = Layout::vertical([Constraint::Length(4), Constraint::Length(17)])
+                .margin(2)
+                .flex(Flex::Start);
+            let [title, body] = main.areas(main_area);
+            let [inputs, preview] =
+                Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                    .spacing(1)
+                    .areas(body);
+            let [schedule, job, options, description, ai] = Layout::vertical([
+                Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Length(3),
             ])
-            .margin(2)
-            .flex(Flex::Start);
-            let [title, cron_notation, job, description, ai] = main.areas(main_area);
+            .areas(inputs);
+            let [cron_notation, timezone] =
+                Layout::horizontal([Constraint::Percentage(65), Constraint::Percentage(35)])
+                    .spacing(1)
+                    .areas(schedule);
+            self.draw_preview(frame, preview);
+            self.draw_options(frame, options);
 
             let footer = Layout::vertical([Constraint::Length(3)]);
             let [help] = footer.areas(footer_area);
@@ -324,17 +675,53 @@ impl Component for CronPopup {
                 );
             frame.render_widget(title_widget, title);
 
-            self.draw_footer(
-                frame,
-                help,
-                vec![
-                    ("<Esc>", "Close without saving"),
-                    ("<Tab>", "Move to the next field"),
-                    ("<Enter>", "Save and close"),
-                ],
-            )
-            .unwrap();
+            let mut keybinds = vec![
+                ("<Esc>", "Close without saving"),
+                ("<Tab>", "Move to the next field"),
+                ("<Enter>", "Save and close"),
+            ];
+            match self.current_input {
+                ActiveInput::AIQuestion => keybinds.push(("<↑>", "Convert to cron notation")),
+                ActiveInput::Lock | ActiveInput::Log => keybinds.push(("<Space>", "Toggle")),
+                _ => {}
+            }
+            self.draw_footer(frame, help, keybinds).unwrap();
+
+            let timezone_selected = self.current_input == ActiveInput::Timezone;
+            let (timezone_title, timezone_border) = match parse_timezone(&self.timezone_value) {
+                Err(message) => (
+                    format!("Timezone ({})", message),
+                    self.styles.invalid_input_style,
+                ),
+                Ok(_) if self.timezone_value.trim().is_empty() => (
+                    "Timezone (default)".to_string(),
+                    self.styles.unselected_input_border_style,
+                ),
+                Ok(_) => (
+                    "Timezone (CRON_TZ)".to_string(),
+                    self.styles.valid_input_style,
+                ),
+            };
+            self.timezone.set_cursor_style(if timezone_selected {
+                self.styles.cursor_style
+            } else {
+                Style::default()
+            });
+            self.timezone.set_block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(
+                        if timezone_selected && timezone_border != self.styles.invalid_input_style {
+                            self.styles.selected_input_border_style
+                        } else {
+                            timezone_border
+                        },
+                    )
+                    .title(timezone_title),
+            );
+            frame.render_widget(&self.timezone, timezone);
 
+            let ai_title = self.ai_title();
             let cron_input: &mut TextArea<'_> = &mut self.cron_notation;
             let job_input = &mut self.job;
             let description_input = &mut self.job_description;
@@ -342,7 +729,7 @@ impl Component for CronPopup {
 
             match self.current_input {
                 ActiveInput::CronNotation => {
-                    match validate(cron_input) {
+                    match validate(cron_input, self.config.settings.cron.seconds) {
                         Ok(_) => {
                             cron_input.set_block(
                                 Block::default()
@@ -387,7 +774,7 @@ impl Component for CronPopup {
                         Block::default()
                             .borders(Borders::ALL)
                             .border_style(self.styles.unselected_input_border_style)
-                            .title("Ask AI"),
+                            .title(ai_title.clone()),
                     );
                     frame.render_widget(&*ai_question_input, ai);
                 }
@@ -424,7 +811,7 @@ impl Component for CronPopup {
                         Block::default()
                             .borders(Borders::ALL)
                             .border_style(self.styles.unselected_input_border_style)
-                            .title("Ask AI"),
+                            .title(ai_title.clone()),
                     );
                     frame.render_widget(&*ai_question_input, ai);
                 }
@@ -461,7 +848,47 @@ impl Component for CronPopup {
                         Block::default()
                             .borders(Borders::ALL)
                             .border_style(self.styles.unselected_input_border_style)
-                            .title("Ask AI"),
+                            .title(ai_title.clone()),
+                    );
+                    frame.render_widget(&*ai_question_input, ai);
+                }
+                ActiveInput::Timezone
+                | ActiveInput::Lock
+                | ActiveInput::Timeout
+                | ActiveInput::Log => {
+                    cron_input.set_cursor_style(Style::default());
+                    cron_input.set_block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .border_style(self.styles.unselected_input_border_style)
+                            .title("Cron notation*"),
+                    );
+                    frame.render_widget(&*cron_input, cron_notation);
+
+                    job_input.set_cursor_style(Style::default());
+                    job_input.set_block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .border_style(self.styles.unselected_input_border_style)
+                            .title("Job"),
+                    );
+                    frame.render_widget(&*job_input, job);
+
+                    description_input.set_cursor_style(Style::default());
+                    description_input.set_block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .border_style(self.styles.unselected_input_border_style)
+                            .title("Description"),
+                    );
+                    frame.render_widget(&*description_input, description);
+
+                    ai_question_input.set_cursor_style(Style::default());
+                    ai_question_input.set_block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .border_style(self.styles.unselected_input_border_style)
+                            .title(ai_title.clone()),
                     );
                     frame.render_widget(&*ai_question_input, ai);
                 }
@@ -471,7 +898,7 @@ impl Component for CronPopup {
                         Block::default()
                             .borders(Borders::ALL)
                             .border_style(self.styles.selected_input_border_style)
-                            .title("Ask AI"),
+                            .title(ai_title.clone()),
                     );
                     frame.render_widget(&*ai_question_input, ai);
 
@@ -509,22 +936,11 @@ impl Component for CronPopup {
     }
 }
 
-fn center(area: Rect, horizontal: Constraint, vertical: Constraint) -> Rect {
-    let [area] = Layout::horizontal([horizontal])
-        .flex(Flex::Center)
-        .areas(area);
-    let [area] = Layout::vertical([vertical]).flex(Flex::Center).areas(area);
-    area
-}
-
 pub enum ValidationError {
     InvalidCronExpression(String),
 }
 
-fn validate(textarea: &mut TextArea) -> Result<(), ValidationError> {
-    use chrono::Utc;
-    use cron_parser::parse;
-
+fn validate(textarea: &mut TextArea, allow_seconds: bool) -> Result<(), ValidationError> {
     let input = textarea
         .lines()
         .first()
@@ -532,18 +948,14 @@ fn validate(textarea: &mut TextArea) -> Result<(), ValidationError> {
         .unwrap_or("")
         .trim();
 
-    let now = Utc::now();
-
-    match parse(input, &now) {
+    match validate_cron_expression(input, allow_seconds) {
         Ok(_) => {
             textarea.set_style(Style::default().fg(Color::LightGreen));
             Ok(())
         }
-        Err(_) => {
+        Err(message) => {
             textarea.set_style(Style::default().fg(Color::LightRed));
-            Err(ValidationError::InvalidCronExpression(
-                "Invalid cron expression".to_string(),
-            ))
+            Err(ValidationError::InvalidCronExpression(message))
         }
     }
 }
@@ -0,0 +1,116 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{cron::diff::diff_to_lines, Component};
+use crate::{
+    action::{Action, Module},
+    config::Config,
+    draw::{center, Drawable},
+    style::EditWindowStyles,
+};
+
+/// Shows what a change will do to the crontab and waits for it to be confirmed.
+#[derive(Default)]
+pub struct CronReview {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    enabled: bool,
+    styles: EditWindowStyles,
+    lines: Vec<Line<'static>>,
+    scroll: u16,
+}
+
+impl Drawable for CronReview {}
+
+impl CronReview {
+    pub fn new() -> Self {
+        Self {
+            styles: EditWindowStyles::new(),
+            ..Self::default()
+        }
+    }
+}
+
+impl Component for CronReview {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::PassReview(ref old, ref new) = action {
+            self.lines = diff_to_lines(old, new);
+            self.scroll = 0;
+        }
+        if let Action::ChangeMode(Module::CronReview) = action {
+            self.enabled = true;
+        }
+        if self.enabled {
+            match action {
+                // `Cron` writes the changes or reopens their editor; the dialog only has to close.
+                Action::ChangeMode(Module::Cron) | Action::WriteCrontab | Action::CancelReview => {
+                    self.enabled = false;
+                }
+                Action::MoveUp => {
+                    self.scroll = self.scroll.saturating_sub(1);
+                }
+                Action::MoveDown => {
+                    self.scroll = self.scroll.saturating_add(1);
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, _area: Rect) -> Result<()> {
+        if self.enabled {
+            let area = center(
+                frame.area(),
+                Constraint::Percentage(70),
+                Constraint::Percentage(80),
+            );
+            frame.render_widget(Clear, area);
+
+            let [main_area, footer_area] =
+                Layout::vertical([Constraint::Min(3), Constraint::Length(2)])
+                    .flex(Flex::SpaceBetween)
+                    .areas(area);
+
+            let diff = Paragraph::new(Text::from(self.lines.clone()))
+                .style(self.styles.title_style)
+                .block(
+                    Block::default()
+                        .style(self.styles.window_style)
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Thick)
+                        .border_style(self.styles.window_border_style)
+                        .title(" Review changes: current crontab (-) and new (+) "),
+                )
+                .scroll((self.scroll, 0));
+            frame.render_widget(diff, main_area);
+
+            self.draw_footer(
+                frame,
+                footer_area,
+                vec![
+                    ("<Enter>", "Write"),
+                    ("<Esc>", "Back to editing"),
+                    ("<↓↑>", "Scroll"),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    prelude::Frame,
+    text::Text,
+    widgets::{Block, BorderType, Borders, Clear, List, ListState},
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{cron::source::list_crontab_sources, Component};
+use crate::{
+    action::{Action, Module},
+    components::cron::source::CrontabSource,
+    config::Config,
+    draw::{center, Drawable},
+    style::{EditWindowStyles, TableStyles},
+};
+
+#[derive(Default)]
+pub struct CronSources {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    enabled: bool,
+    styles: EditWindowStyles,
+    table_styles: TableStyles,
+    sources: Vec<CrontabSource>,
+    current: CrontabSource,
+    state: ListState,
+}
+
+impl Drawable for CronSources {}
+
+impl CronSources {
+    pub fn new() -> Self {
+        Self {
+            command_tx: None,
+            config: Config::default(),
+            enabled: false,
+            styles: EditWindowStyles::new(),
+            table_styles: TableStyles::new(),
+            sources: vec![],
+            current: CrontabSource::default(),
+            state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    fn draw_list(&mut self, frame: &mut Frame, area: Rect) {
+        let items: Vec<Text> = self
+            .sources
+            .iter()
+            .map(|source| {
+                let marker = if *source == self.current {
+                    "● "
+                } else {
+                    "  "
+                };
+                Text::from(format!(" {}{}", marker, source.label()))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .style(self.styles.window_style)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick)
+                    .border_style(self.styles.window_border_style)
+                    .title(" Select crontab "),
+            )
+            .style(self.styles.title_style)
+            .highlight_style(self.table_styles.selected_row_style);
+
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
+
+impl Component for CronSources {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::SwitchCrontabSource(ref source) = action {
+            self.current = source.clone();
+        }
+        if let Action::ChangeMode(Module::CronSources) = action {
+            self.sources = list_crontab_sources();
+            let selected = self
+                .sources
+                .iter()
+                .position(|source| *source == self.current)
+                .unwrap_or(0);
+            self.state.select(Some(selected));
+            self.enabled = true;
+        }
+        if self.enabled {
+            match action {
+                Action::ChangeMode(Module::Cron) => {
+                    self.enabled = false;
+                }
+                Action::Confirm => {
+                    if let Some(source) = self
+                        .state
+                        .selected()
+                        .and_then(|index| self.sources.get(index))
+                    {
+                        let tx = self.command_tx.clone().unwrap();
+                        tx.send(Action::SwitchCrontabSource(source.clone()))?;
+                    }
+                    self.enabled = false;
+                    return Ok(Some(Action::ChangeMode(Module::Cron)));
+                }
+                Action::MoveUp => {
+                    self.state.select_previous();
+                }
+                Action::MoveDown => {
+                    self.state.select_next();
+                }
+                Action::MoveToTheFirst => {
+                    self.state.select_first();
+                }
+                Action::MoveToTheLast => {
+                    self.state.select_last();
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, _area: Rect) -> Result<()> {
+        if self.enabled {
+            let height = (self.sources.len() as u16 + 2).clamp(5, 20);
+            let area = center(
+                frame.area(),
+                Constraint::Percentage(50),
+                Constraint::Length(height + 2),
+            );
+            frame.render_widget(Clear, area);
+
+            let [list_area, footer_area] =
+                Layout::vertical([Constraint::Length(height), Constraint::Length(2)])
+                    .flex(Flex::SpaceBetween)
+                    .areas(area);
+
+            self.draw_list(frame, list_area);
+            self.draw_footer(
+                frame,
+                footer_area,
+                vec![
+                    ("<Esc>", "Close"),
+                    ("<↓↑>", "Move up and down"),
+                    ("<Enter>", "Open crontab"),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+}
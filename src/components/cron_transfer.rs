@@ -0,0 +1,228 @@
+use color_eyre::Result;
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Flex, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Style},
+    text::{Line, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+use tokio::sync::mpsc::UnboundedSender;
+use tui_textarea::TextArea;
+
+use super::Component;
+use crate::{
+    action::{Action, Module},
+    config::Config,
+    draw::{center, Drawable},
+    style::EditWindowStyles,
+};
+
+const DEFAULT_FILE: &str = "crontab.yaml";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    #[default]
+    Export,
+    Import,
+}
+
+/// Asks for a JSON or YAML file to export the jobs to, or to import them from. An import shows
+/// what it would change and is only applied once confirmed.
+pub struct CronTransfer {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    enabled: bool,
+    styles: EditWindowStyles,
+    direction: Direction,
+    path: TextArea<'static>,
+    /// The table's answer for the current path: the import's changes or the export's outcome.
+    result: Option<Result<Vec<String>, String>>,
+}
+
+impl Drawable for CronTransfer {}
+
+impl Default for CronTransfer {
+    fn default() -> Self {
+        let styles = EditWindowStyles::new();
+        let mut path = TextArea::new(vec![DEFAULT_FILE.to_string()]);
+        path.set_cursor_line_style(Style::default());
+        path.set_cursor_style(styles.cursor_style);
+        path.move_cursor(tui_textarea::CursorMove::End);
+        Self {
+            command_tx: None,
+            config: Config::default(),
+            enabled: false,
+            styles,
+            direction: Direction::default(),
+            path,
+            result: None,
+        }
+    }
+}
+
+impl CronTransfer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn open(&mut self, direction: Direction) -> Option<Action> {
+        self.direction = direction;
+        self.result = None;
+        self.enabled = true;
+        Some(Action::ChangeMode(Module::CronTransfer))
+    }
+
+    fn path(&self) -> String {
+        self.path.lines().join("").trim().to_string()
+    }
+
+    fn confirm(&mut self) -> Result<Option<Action>> {
+        let tx = self.command_tx.clone().unwrap();
+        match (&self.result, self.direction) {
+            (Some(Ok(_)), Direction::Import) => {
+                self.enabled = false;
+                tx.send(Action::ApplyImport)?;
+            }
+            (Some(Ok(_)), Direction::Export) => {
+                self.enabled = false;
+                return Ok(Some(Action::ChangeMode(Module::Cron)));
+            }
+            _ if self.path().is_empty() => {}
+            (_, Direction::Export) => tx.send(Action::ExportToFile(self.path()))?,
+            (_, Direction::Import) => tx.send(Action::PreviewImport(self.path()))?,
+        }
+        Ok(None)
+    }
+
+    fn result_lines(&self) -> Vec<Line<'static>> {
+        match &self.result {
+            None => vec![Line::raw(
+                "The format follows the extension: .json, .yaml or .yml",
+            )],
+            Some(Err(message)) => message
+                .lines()
+                .map(|line| Line::styled(line.to_string(), self.styles.invalid_input_style))
+                .collect(),
+            Some(Ok(lines)) if lines.is_empty() => {
+                vec![Line::raw("The crontab already matches the file")]
+            }
+            Some(Ok(lines)) => lines
+                .iter()
+                .map(|line| {
+                    let color = match line.chars().next() {
+                        Some('+') => Color::LightGreen,
+                        Some('-') => Color::LightRed,
+                        Some('~') => Color::LightYellow,
+                        _ => Color::Reset,
+                    };
+                    Line::styled(line.clone(), Style::default().fg(color))
+                })
+                .collect(),
+        }
+    }
+
+    fn block(&self, title: &str) -> Block<'static> {
+        Block::default()
+            .style(self.styles.window_style)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(self.styles.window_border_style)
+            .title(title.to_string())
+    }
+}
+
+impl Component for CronTransfer {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.enabled {
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter => {}
+                _ => {
+                    // Another file needs another preview.
+                    if self.path.input(key) {
+                        self.result = None;
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ExportJobs if !self.enabled => return Ok(self.open(Direction::Export)),
+            Action::ImportJobs if !self.enabled => return Ok(self.open(Direction::Import)),
+            _ => {}
+        }
+        if self.enabled {
+            match action {
+                Action::ChangeMode(Module::Cron) => {
+                    self.enabled = false;
+                }
+                Action::TransferResult(result) => {
+                    self.result = Some(result);
+                }
+                Action::Confirm => {
+                    return self.confirm();
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, _area: Rect) -> Result<()> {
+        if self.enabled {
+            let area = center(
+                frame.area(),
+                Constraint::Percentage(70),
+                Constraint::Percentage(60),
+            );
+            frame.render_widget(Clear, area);
+
+            let [path_area, result_area, footer_area] = Layout::vertical([
+                Constraint::Length(3),
+                Constraint::Min(3),
+                Constraint::Length(2),
+            ])
+            .flex(Flex::SpaceBetween)
+            .areas(area);
+
+            let (title, confirm) = match (self.direction, &self.result) {
+                (Direction::Export, Some(Ok(_))) => (" Export jobs to ", "Close"),
+                (Direction::Export, _) => (" Export jobs to ", "Export"),
+                (Direction::Import, Some(Ok(_))) => (" Import jobs from ", "Apply the changes"),
+                (Direction::Import, _) => (" Import jobs from ", "Show the changes"),
+            };
+            self.path.set_block(self.block(title));
+            frame.render_widget(&self.path, path_area);
+
+            let result_title = match self.direction {
+                Direction::Export => " Export ",
+                Direction::Import => " Changes to the crontab ",
+            };
+            let result = Paragraph::new(Text::from(self.result_lines()))
+                .wrap(Wrap { trim: false })
+                .style(self.styles.title_style)
+                .block(self.block(result_title));
+            frame.render_widget(result, result_area);
+
+            self.draw_footer(
+                frame,
+                footer_area,
+                vec![("<Esc>", "Close"), ("<Enter>", confirm)],
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Layout, Rect},
+    prelude::Frame,
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear},
+};
+use tui_textarea::{CursorMove, TextArea};
+
+use crate::style::EditWindowStyles;
+
+/// One-line inputs stacked in a window, one of them taking the keys.
+pub struct Form {
+    fields: Vec<(&'static str, TextArea<'static>)>,
+    active: usize,
+    /// Shown under the inputs until the next key.
+    pub error: Option<String>,
+    styles: EditWindowStyles,
+}
+
+impl Form {
+    /// A form with the given labels and initial values.
+    pub fn new(fields: &[(&'static str, &str)]) -> Self {
+        let styles = EditWindowStyles::new();
+        let fields = fields
+            .iter()
+            .map(|(label, value)| {
+                let mut input = TextArea::new(vec![value.to_string()]);
+                input.set_cursor_line_style(Style::default());
+                input.move_cursor(CursorMove::End);
+                (*label, input)
+            })
+            .collect();
+        Self {
+            fields,
+            active: 0,
+            error: None,
+            styles,
+        }
+    }
+
+    /// Hides what is typed into the field, for passwords.
+    pub fn set_masked(&mut self, field: usize) {
+        self.fields[field].1.set_mask_char('•');
+    }
+
+    pub fn value(&self, field: usize) -> String {
+        self.fields[field].1.lines().join("")
+    }
+
+    pub fn next_field(&mut self) {
+        self.active = (self.active + 1) % self.fields.len();
+    }
+
+    pub fn input(&mut self, key: KeyEvent) {
+        if self.fields[self.active].1.input(key) {
+            self.error = None;
+        }
+    }
+
+    /// Height of the window: three rows per input, two for the error and the borders.
+    pub fn height(&self) -> u16 {
+        self.fields.len() as u16 * 3 + 3
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect, title: &str) {
+        frame.render_widget(Clear, area);
+        let block = Block::default()
+            .style(self.styles.window_style)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(self.styles.window_border_style)
+            .title(title.to_string());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut constraints = vec![Constraint::Length(3); self.fields.len()];
+        constraints.push(Constraint::Length(1));
+        let areas = Layout::vertical(constraints).split(inner);
+
+        for (i, (label, input)) in self.fields.iter_mut().enumerate() {
+            let (border_style, cursor_style) = if i == self.active {
+                (
+                    self.styles.selected_input_border_style,
+                    self.styles.cursor_style,
+                )
+            } else {
+                (self.styles.unselected_input_border_style, Style::default())
+            };
+            input.set_cursor_style(cursor_style);
+            input.set_block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(border_style)
+                    .title(*label),
+            );
+            frame.render_widget(&*input, areas[i]);
+        }
+
+        if let Some(error) = &self.error {
+            frame.render_widget(
+                Line::styled(format!(" {}", error), self.styles.invalid_input_style),
+                areas[self.fields.len()],
+            );
+        }
+    }
+}
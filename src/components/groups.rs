@@ -0,0 +1,433 @@
+use std::io;
+
+use color_eyre::Result;
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Layout, Rect},
+    prelude::Frame,
+    text::Line,
+};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::error;
+
+use super::{
+    form::Form,
+    users::groups::{self, read_groups, read_gshadow, Group, GroupShadow},
+    Component,
+};
+use crate::{
+    action::{Action, Module},
+    config::Config,
+    draw::{center, draw_confirmation, draw_records, Drawable, Record, RecordSelection},
+    style::{EditWindowStyles, TableStyles},
+};
+
+impl Drawable for Groups {}
+const TITLES: [&str; 5] = ["Group", "GID", "Members", "Admins", "Password"];
+
+/// The form open over the table.
+enum GroupForm {
+    New,
+    AddMember(String),
+    RemoveMember(String),
+}
+
+/// A change to the groups, shown with its command before it runs.
+enum GroupChange {
+    Create {
+        name: String,
+        gid: Option<String>,
+    },
+    Delete {
+        name: String,
+    },
+    Member {
+        group: String,
+        user: String,
+        add: bool,
+    },
+}
+
+impl GroupChange {
+    fn question(&self) -> String {
+        match self {
+            GroupChange::Create { name, .. } => format!("Create the group {}?", name),
+            GroupChange::Delete { name } => format!("Delete the group {}?", name),
+            GroupChange::Member {
+                group,
+                user,
+                add: true,
+            } => format!("Add {} to {}?", user, group),
+            GroupChange::Member { group, user, .. } => format!("Remove {} from {}?", user, group),
+        }
+    }
+
+    fn command(&self) -> String {
+        match self {
+            GroupChange::Create { name, gid } => format!(
+                "groupadd {}",
+                groups::groupadd_args(name, gid.as_deref()).join(" ")
+            ),
+            GroupChange::Delete { name } => format!("groupdel -- {}", name),
+            GroupChange::Member { group, user, add } => format!(
+                "gpasswd {}",
+                groups::member_args(group, user, *add).join(" ")
+            ),
+        }
+    }
+
+    fn apply(&self) -> io::Result<String> {
+        match self {
+            GroupChange::Create { name, gid } => {
+                groups::create(name, gid.as_deref()).map(|()| format!("Created {}", name))
+            }
+            GroupChange::Delete { name } => {
+                groups::delete(name).map(|()| format!("Deleted {}", name))
+            }
+            GroupChange::Member { group, user, add } => {
+                groups::set_member(group, user, *add).map(|()| {
+                    if *add {
+                        format!("Added {} to {}", user, group)
+                    } else {
+                        format!("Removed {} from {}", user, group)
+                    }
+                })
+            }
+        }
+    }
+}
+
+/// Lists the groups of `/etc/group` with their members, creates and deletes groups and changes
+/// who is in them.
+#[derive(Default)]
+pub struct Groups {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    enabled: bool,
+    items: Vec<Group>,
+    /// The administrators and passwords of the groups, when `/etc/gshadow` is readable.
+    gshadow: Option<Vec<GroupShadow>>,
+    selection: RecordSelection,
+    /// The form open over the table, if any.
+    editing: Option<(GroupForm, Form)>,
+    /// The change waiting for confirmation.
+    change: Option<GroupChange>,
+    /// What the command printed when the confirmed change failed.
+    failure: Option<String>,
+    /// The outcome of the last change, or why the groups could not be read.
+    message: Option<String>,
+    styles: TableStyles,
+    window_styles: EditWindowStyles,
+}
+
+impl Groups {
+    pub fn new() -> Self {
+        Self {
+            styles: TableStyles::new(),
+            window_styles: EditWindowStyles::new(),
+            ..Self::default()
+        }
+    }
+
+    fn load(&mut self) {
+        self.items = match read_groups() {
+            Ok(groups) => groups,
+            Err(e) => {
+                error!("Error reading {}: {}", groups::GROUP, e);
+                self.message = Some(format!("Error: {}", e));
+                Vec::new()
+            }
+        };
+        self.gshadow = read_gshadow().ok();
+        self.selection.set_len(self.items.len());
+    }
+
+    fn selected(&self) -> Option<&Group> {
+        self.selection.selected().and_then(|i| self.items.get(i))
+    }
+
+    /// The admins and password cells of the group, "?" when `/etc/gshadow` is not readable.
+    fn shadow_cells(&self, name: &str) -> [String; 2] {
+        let Some(gshadow) = &self.gshadow else {
+            return ["?".to_string(), "?".to_string()];
+        };
+        match gshadow.iter().find(|entry| entry.name == name) {
+            Some(entry) => [
+                entry.admins.join(", "),
+                if entry.has_password() { "set" } else { "none" }.to_string(),
+            ],
+            None => [String::new(), String::new()],
+        }
+    }
+
+    fn open_form(&mut self, kind: GroupForm) -> Option<Action> {
+        let form = match &kind {
+            GroupForm::New => Form::new(&[("Name", ""), ("GID (empty for the next free one)", "")]),
+            GroupForm::AddMember(_) | GroupForm::RemoveMember(_) => Form::new(&[("User", "")]),
+        };
+        self.editing = Some((kind, form));
+        Some(Action::ChangeMode(Module::GroupsEdit))
+    }
+
+    /// Turns the form into a change to confirm, or shows what is wrong with it.
+    fn submit_form(&mut self) -> Option<Action> {
+        let (kind, form) = self.editing.as_mut()?;
+        let add = matches!(kind, GroupForm::AddMember(_));
+        let change = match kind {
+            GroupForm::New => {
+                let name = form.value(0).trim().to_string();
+                let gid = form.value(1).trim().to_string();
+                if let Err(e) = groups::validate_name(&name) {
+                    form.error = Some(e);
+                    return None;
+                }
+                if self.items.iter().any(|group| group.name == name) {
+                    form.error = Some(format!("The group {} already exists", name));
+                    return None;
+                }
+                if !gid.is_empty() && gid.parse::<u32>().is_err() {
+                    form.error = Some("The GID is a number".to_string());
+                    return None;
+                }
+                GroupChange::Create {
+                    name,
+                    gid: Some(gid).filter(|gid| !gid.is_empty()),
+                }
+            }
+            GroupForm::AddMember(group) | GroupForm::RemoveMember(group) => {
+                let user = form.value(0).trim().to_string();
+                let is_member = self
+                    .items
+                    .iter()
+                    .find(|item| &item.name == group)
+                    .is_some_and(|item| item.members.contains(&user));
+                if user.is_empty() {
+                    form.error = Some("The user is needed".to_string());
+                    return None;
+                }
+                if add && is_member {
+                    form.error = Some(format!("{} is already in {}", user, group));
+                    return None;
+                }
+                if !add && !is_member {
+                    form.error = Some(format!("{} is not in {}", user, group));
+                    return None;
+                }
+                GroupChange::Member {
+                    group: group.clone(),
+                    user,
+                    add,
+                }
+            }
+        };
+        self.editing = None;
+        Some(self.confirm(change))
+    }
+
+    fn confirm(&mut self, change: GroupChange) -> Action {
+        self.change = Some(change);
+        self.failure = None;
+        Action::ChangeMode(Module::GroupsConfirm)
+    }
+
+    fn apply_change(&mut self) -> Option<Action> {
+        let change = self.change.as_ref()?;
+        match change.apply() {
+            Ok(message) => {
+                self.message = Some(message);
+                self.change = None;
+                Some(Action::ChangeMode(Module::Groups))
+            }
+            Err(e) => {
+                error!("Error changing a group: {}", e);
+                self.failure = Some(e.to_string());
+                None
+            }
+        }
+    }
+
+    fn draw_title(&self, frame: &mut Frame, area: Rect) {
+        let mut title = format!(" Groups  ·  {} listed", self.items.len());
+        if self.gshadow.is_none() {
+            title.push_str(&format!("  ·  {} is not readable", groups::GSHADOW));
+        }
+        if let Some(message) = &self.message {
+            title.push_str(&format!("  ·  {}", message));
+        }
+        frame.render_widget(Line::from(title).style(self.styles.header_style), area);
+    }
+
+    fn draw_table(&mut self, frame: &mut Frame, area: Rect) {
+        let records: Vec<Record> = self
+            .items
+            .iter()
+            .map(|group| Record {
+                cells: [
+                    group.name.clone(),
+                    group.gid.to_string(),
+                    group.members.join(", "),
+                ]
+                .into_iter()
+                .chain(self.shadow_cells(&group.name))
+                .collect(),
+                dimmed: false,
+                marks: Vec::new(),
+            })
+            .collect();
+        draw_records(
+            frame,
+            area,
+            &TITLES,
+            &records,
+            &mut self.selection,
+            &self.styles,
+        );
+    }
+}
+
+impl Component for Groups {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if let Some((_, form)) = self.editing.as_mut() {
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Tab => {}
+                _ => form.input(key),
+            }
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::ChangeMode(Module::Groups) = action {
+            if !self.enabled {
+                self.message = None;
+            }
+            self.editing = None;
+            self.change = None;
+            self.load();
+            self.enabled = true;
+        }
+        if !self.enabled {
+            return Ok(None);
+        }
+        if let Some((_, form)) = self.editing.as_mut() {
+            match action {
+                Action::SwitchElement => form.next_field(),
+                Action::Confirm => return Ok(self.submit_form()),
+                _ => {}
+            }
+            return Ok(None);
+        }
+        if self.change.is_some() {
+            if let Action::Confirm = action {
+                return Ok(self.apply_change());
+            }
+            return Ok(None);
+        }
+        match action {
+            Action::ChangeMode(Module::Home) => {
+                self.enabled = false;
+                return Ok(Some(Action::ClearScreen));
+            }
+            Action::NewRecord => return Ok(self.open_form(GroupForm::New)),
+            Action::DeleteRecord => {
+                if let Some(group) = self.selected() {
+                    let change = GroupChange::Delete {
+                        name: group.name.clone(),
+                    };
+                    return Ok(Some(self.confirm(change)));
+                }
+            }
+            Action::AddMember => {
+                if let Some(group) = self.selected().map(|group| group.name.clone()) {
+                    return Ok(self.open_form(GroupForm::AddMember(group)));
+                }
+            }
+            Action::RemoveMember => {
+                if let Some(group) = self.selected().map(|group| group.name.clone()) {
+                    return Ok(self.open_form(GroupForm::RemoveMember(group)));
+                }
+            }
+            Action::MoveUp => self.selection.previous(),
+            Action::MoveDown => self.selection.next(),
+            Action::MoveToTheFirst => self.selection.first(),
+            Action::MoveToTheLast => self.selection.last(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if self.enabled {
+            let vertical = Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(2),
+            ]);
+            let rects = vertical.split(area);
+
+            self.draw_title(frame, rects[0]);
+            self.draw_table(frame, rects[1]);
+
+            if let Some((kind, form)) = self.editing.as_mut() {
+                let title = match kind {
+                    GroupForm::New => " New group ".to_string(),
+                    GroupForm::AddMember(group) => format!(" Add a member to {} ", group),
+                    GroupForm::RemoveMember(group) => format!(" Remove a member from {} ", group),
+                };
+                let popup = center(
+                    frame.area(),
+                    Constraint::Percentage(60),
+                    Constraint::Length(form.height()),
+                );
+                form.draw(frame, popup, &title);
+                self.draw_footer(
+                    frame,
+                    rects[2],
+                    vec![
+                        ("<Esc>", "Close"),
+                        ("<Tab>", "Next field"),
+                        ("<Enter>", "Continue"),
+                    ],
+                )?;
+            } else if let Some(change) = &self.change {
+                draw_confirmation(
+                    frame,
+                    &change.question(),
+                    &[change.command()],
+                    &[],
+                    self.failure.as_deref(),
+                    &self.window_styles,
+                );
+                self.draw_footer(
+                    frame,
+                    rects[2],
+                    vec![("<Esc>", "Cancel"), ("<y>", "Yes, run it")],
+                )?;
+            } else {
+                self.draw_footer(
+                    frame,
+                    rects[2],
+                    vec![
+                        ("<Esc>", "Quit"),
+                        ("<↓↑>", "Move up and down"),
+                        ("<n>", "New group"),
+                        ("<d>", "Delete"),
+                        ("<a>", "Add member"),
+                        ("<r>", "Remove member"),
+                    ],
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
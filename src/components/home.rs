@@ -47,10 +47,26 @@ impl Home {
                 label: "🕗 Cron Jobs",
                 action: || Action::ChangeMode(Module::Cron),
             },
+            MenuItem {
+                label: "⏲️ Systemd Timers",
+                action: || Action::ChangeMode(Module::Timers),
+            },
+            MenuItem {
+                label: "📅 Anacron",
+                action: || Action::ChangeMode(Module::Anacron),
+            },
+            MenuItem {
+                label: "⏱️ At Jobs",
+                action: || Action::ChangeMode(Module::AtJobs),
+            },
             MenuItem {
                 label: "👤 Users",
                 action: || Action::ChangeMode(Module::Users),
             },
+            MenuItem {
+                label: "👥 Groups",
+                action: || Action::ChangeMode(Module::Groups),
+            },
             MenuItem {
                 label: "🐬 MySQL",
                 action: || Action::ChangeMode(Module::MySql),
@@ -68,6 +68,14 @@ impl Settings {
                     &self.config.settings.cron.timezone
                 },
             ),
+            (
+                "Seconds Field:",
+                if self.config.settings.cron.seconds {
+                    "Enabled"
+                } else {
+                    "Disabled"
+                },
+            ),
             (
                 "Document Root:",
                 if self.config.settings.users.docroot.is_empty() {
@@ -0,0 +1,306 @@
+pub mod systemd;
+
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Margin, Rect},
+    prelude::Frame,
+    text::{Line, Text},
+    widgets::{
+        Cell, HighlightSpacing, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table,
+        TableState,
+    },
+};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::error;
+use unicode_width::UnicodeWidthStr;
+
+use super::{timers::systemd::Timer, Component};
+use crate::{
+    action::{Action, Module},
+    config::Config,
+    draw::Drawable,
+    style::TableStyles,
+};
+
+impl Drawable for Timers {}
+const ITEM_HEIGHT: usize = 3;
+const TITLES: [&str; 6] = ["Unit", "Schedule", "Next", "Last", "Activates", "State"];
+
+/// Lists the systemd timers and enables, disables or starts them.
+#[derive(Default)]
+pub struct Timers {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    enabled: bool,
+    state: TableState,
+    items: Vec<Timer>,
+    /// The outcome of the last command, or why the timers could not be listed.
+    message: Option<String>,
+    scroll_state: ScrollbarState,
+    styles: TableStyles,
+}
+
+impl Timers {
+    pub fn new() -> Self {
+        Self {
+            state: TableState::default().with_selected(0),
+            styles: TableStyles::new(),
+            ..Self::default()
+        }
+    }
+
+    fn load(&mut self) {
+        self.items = match systemd::list_timers() {
+            Ok(timers) => timers,
+            Err(e) => {
+                error!("Error listing systemd timers: {}", e);
+                self.message = Some(format!("Error: {}", e));
+                Vec::new()
+            }
+        };
+        let selected = self
+            .state
+            .selected()
+            .unwrap_or(0)
+            .min(self.items.len().saturating_sub(1));
+        self.state.select(Some(selected));
+        self.scroll_state = ScrollbarState::new(self.items.len().saturating_sub(1) * ITEM_HEIGHT)
+            .position(selected * ITEM_HEIGHT);
+    }
+
+    fn selected(&self) -> Option<&Timer> {
+        self.state.selected().and_then(|i| self.items.get(i))
+    }
+
+    fn toggle(&mut self) {
+        let Some(timer) = self.selected() else {
+            return;
+        };
+        let unit = timer.unit.clone();
+        let enable = !timer.is_enabled();
+        if enable && timer.state != "disabled" {
+            self.message = Some(format!(
+                "{} is {} and can't be enabled or disabled",
+                unit, timer.state
+            ));
+            return;
+        }
+        self.message = Some(match systemd::set_enabled(&unit, enable) {
+            Ok(()) if enable => format!("Enabled and started {}", unit),
+            Ok(()) => format!("Disabled and stopped {}", unit),
+            Err(e) => {
+                error!("Error toggling systemd timer: {}", e);
+                format!("Error: {}", e)
+            }
+        });
+        self.load();
+    }
+
+    fn start(&mut self) {
+        let Some(unit) = self.selected().map(|timer| timer.unit.clone()) else {
+            return;
+        };
+        self.message = Some(match systemd::start(&unit) {
+            Ok(()) => format!("Started {}", unit),
+            Err(e) => {
+                error!("Error starting systemd timer: {}", e);
+                format!("Error: {}", e)
+            }
+        });
+        self.load();
+    }
+
+    fn draw_title(&self, frame: &mut Frame, area: Rect) {
+        let mut title = format!(" Systemd timers  ·  {} listed", self.items.len());
+        if let Some(message) = &self.message {
+            title.push_str(&format!("  ·  {}", message));
+        }
+        frame.render_widget(Line::from(title).style(self.styles.header_style), area);
+    }
+
+    fn draw_table(&mut self, frame: &mut Frame, area: Rect) {
+        let header = TITLES
+            .into_iter()
+            .map(|title| Cell::from(Text::from(format!("\n{}\n", title))))
+            .collect::<Row>()
+            .style(self.styles.header_style)
+            .height(3);
+
+        let cells: Vec<[String; 6]> = self.items.iter().map(row_cells).collect();
+        let rows = cells
+            .iter()
+            .zip(&self.items)
+            .enumerate()
+            .map(|(i, (cells, timer))| {
+                let color = if i % 2 == 0 {
+                    self.styles.normal_row_color
+                } else {
+                    self.styles.alt_row_color
+                };
+                let style = if timer.is_enabled() || timer.next.is_some() {
+                    self.styles.row_style
+                } else {
+                    self.styles.disabled_row_style
+                };
+                cells
+                    .iter()
+                    .map(|content| Cell::from(Text::from(format!("\n{content}\n"))))
+                    .collect::<Row>()
+                    .style(style.bg(color))
+                    .height(ITEM_HEIGHT.try_into().unwrap())
+            });
+
+        let widths = TITLES.iter().enumerate().map(|(column, title)| {
+            let longest = cells
+                .iter()
+                .map(|cells| cells[column].width())
+                .max()
+                .unwrap_or(0)
+                .max(title.len());
+            #[allow(clippy::cast_possible_truncation)]
+            Constraint::Min(longest as u16 + 2)
+        });
+
+        let bar = " ▌ ";
+        let table = Table::new(rows, widths)
+            .header(header)
+            .row_highlight_style(self.styles.selected_row_style)
+            .style(
+                self.styles
+                    .row_style
+                    .bg(if (self.items.len() + 1).is_multiple_of(2) {
+                        self.styles.alt_row_color
+                    } else {
+                        self.styles.normal_row_color
+                    }),
+            )
+            .highlight_symbol(Text::from(vec!["".into(), bar.into(), "".into()]))
+            .highlight_spacing(HighlightSpacing::Always);
+
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+
+    fn draw_scrollbar(&mut self, frame: &mut Frame, area: Rect) {
+        let scrollbar = Scrollbar::default()
+            .orientation(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .style(self.styles.scrollbar_style);
+
+        frame.render_stateful_widget(
+            scrollbar,
+            area.inner(Margin {
+                vertical: 1,
+                horizontal: 1,
+            }),
+            &mut self.scroll_state,
+        );
+    }
+
+    fn select_row(&mut self, i: usize) {
+        self.state.select(Some(i));
+        self.scroll_state = self.scroll_state.position(i * ITEM_HEIGHT);
+    }
+
+    fn next_row(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) if i + 1 < self.items.len() => i + 1,
+            _ => 0,
+        };
+        self.select_row(i);
+    }
+
+    fn previous_row(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) if i > 0 => i - 1,
+            _ => self.items.len() - 1,
+        };
+        self.select_row(i);
+    }
+}
+
+fn row_cells(timer: &Timer) -> [String; 6] {
+    let with_relative = |time: &Option<String>, relative: &Option<String>| match (time, relative) {
+        (Some(time), Some(relative)) => format!("{} ({})", time, relative),
+        (Some(time), None) => time.clone(),
+        (None, _) => "-".to_string(),
+    };
+    [
+        timer.unit.clone(),
+        timer.schedule_label(),
+        with_relative(&timer.next, &timer.left),
+        with_relative(&timer.last, &timer.passed),
+        timer.activates.clone(),
+        timer.state.clone(),
+    ]
+}
+
+impl Component for Timers {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::ChangeMode(Module::Timers) = action {
+            self.message = None;
+            self.load();
+            self.enabled = true;
+        }
+        if self.enabled {
+            match action {
+                Action::ChangeMode(Module::Home) => {
+                    self.enabled = false;
+                    return Ok(Some(Action::ClearScreen));
+                }
+                Action::MoveUp => self.previous_row(),
+                Action::MoveDown => self.next_row(),
+                Action::MoveToTheFirst if !self.items.is_empty() => self.select_row(0),
+                Action::MoveToTheLast if !self.items.is_empty() => {
+                    self.select_row(self.items.len() - 1)
+                }
+                Action::ToggleRecord => self.toggle(),
+                Action::RunRecord => self.start(),
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if self.enabled {
+            let vertical = Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(2),
+            ]);
+            let rects = vertical.split(area);
+
+            self.draw_title(frame, rects[0]);
+            self.draw_table(frame, rects[1]);
+            self.draw_scrollbar(frame, rects[1]);
+            self.draw_footer(
+                frame,
+                rects[2],
+                vec![
+                    ("<Esc>", "Quit"),
+                    ("<↓↑>", "Move up and down"),
+                    ("<t>", "Enable/disable"),
+                    ("<r>", "Start"),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+}
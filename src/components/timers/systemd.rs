@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+use std::io;
+use std::process::{Command, Stdio};
+
+use chrono::NaiveDate;
+
+/// A systemd `.timer` unit as `systemctl list-timers` and `systemctl show` describe it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Timer {
+    pub unit: String,
+    /// The unit the timer starts, usually a `.service`.
+    pub activates: String,
+    /// e.g. "Sat 2025-03-01 00:00:00 UTC", `None` when nothing is scheduled.
+    pub next: Option<String>,
+    pub left: Option<String>,
+    pub last: Option<String>,
+    pub passed: Option<String>,
+    /// The unit file state: "enabled", "disabled", "static", ...
+    pub state: String,
+    /// `OnCalendar=` and monotonic triggers, as in the unit file.
+    pub schedule: Vec<String>,
+}
+
+impl Timer {
+    pub fn is_enabled(&self) -> bool {
+        self.state == "enabled"
+    }
+
+    pub fn schedule_label(&self) -> String {
+        self.schedule.join("; ")
+    }
+}
+
+/// Lists every timer, loaded or not, together with its schedule and state.
+pub fn list_timers() -> io::Result<Vec<Timer>> {
+    let mut timers = parse_list_timers(&systemctl(&[
+        "list-timers",
+        "--all",
+        "--no-legend",
+        "--no-pager",
+    ])?);
+    if timers.is_empty() {
+        return Ok(timers);
+    }
+
+    let mut args = vec![
+        "show",
+        "--no-pager",
+        "--property=Id,UnitFileState,TimersCalendar,TimersMonotonic",
+        "--",
+    ];
+    args.extend(timers.iter().map(|timer| timer.unit.as_str()));
+    let details = parse_show(&systemctl(&args)?);
+    for timer in &mut timers {
+        if let Some(properties) = details.get(&timer.unit) {
+            timer.state = properties.state.clone();
+            timer.schedule = properties.schedule.clone();
+        }
+    }
+    Ok(timers)
+}
+
+/// Enables or disables the timer and starts or stops it right away.
+pub fn set_enabled(unit: &str, enabled: bool) -> io::Result<()> {
+    let verb = if enabled { "enable" } else { "disable" };
+    systemctl(&[verb, "--now", "--", unit]).map(|_| ())
+}
+
+pub fn start(unit: &str) -> io::Result<()> {
+    systemctl(&["start", "--", unit]).map(|_| ())
+}
+
+fn systemctl(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("systemctl")
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "systemctl {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parses `systemctl list-timers --all --no-legend`.
+///
+/// The columns are NEXT, LEFT, LAST, PASSED, UNIT and ACTIVATES. Only the last two never contain
+/// spaces, so the timestamps are found by their date and the relative times fill the gaps.
+pub fn parse_list_timers(output: &str) -> Vec<Timer> {
+    output.lines().filter_map(parse_timer_line).collect()
+}
+
+fn parse_timer_line(line: &str) -> Option<Timer> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    if words.len() < 6 {
+        return None;
+    }
+    let (times, units) = words.split_at(words.len() - 2);
+    if !units[0].ends_with(".timer") {
+        return None;
+    }
+
+    let (next, rest) = timestamp(times);
+    // LEFT runs up to where LAST starts.
+    let left_len = (1..=rest.len())
+        .find(|&i| starts_timestamp(&rest[i..]))
+        .unwrap_or(rest.len());
+    let (left, rest) = rest.split_at(left_len);
+    let (last, passed) = timestamp(rest);
+
+    Some(Timer {
+        unit: units[0].to_string(),
+        activates: units[1].to_string(),
+        next,
+        left: relative(left),
+        last,
+        passed: relative(passed),
+        ..Timer::default()
+    })
+}
+
+/// Splits a leading timestamp ("Sat 2025-03-01 00:00:00 UTC", "-" or "n/a") off `words`.
+fn timestamp<'a>(words: &'a [&'a str]) -> (Option<String>, &'a [&'a str]) {
+    match words {
+        [_, date, ..] if is_date(date) => {
+            let len = words.len().min(4);
+            (Some(words[..len].join(" ")), &words[len..])
+        }
+        [_, rest @ ..] => (None, rest),
+        [] => (None, words),
+    }
+}
+
+fn starts_timestamp(words: &[&str]) -> bool {
+    match words {
+        [empty, ..] if is_empty(empty) => true,
+        [_, date, ..] => is_date(date),
+        _ => false,
+    }
+}
+
+fn relative(words: &[&str]) -> Option<String> {
+    match words {
+        [] => None,
+        [empty] if is_empty(empty) => None,
+        _ => Some(words.join(" ")),
+    }
+}
+
+fn is_empty(word: &str) -> bool {
+    word == "-" || word == "n/a"
+}
+
+fn is_date(word: &str) -> bool {
+    NaiveDate::parse_from_str(word, "%Y-%m-%d").is_ok()
+}
+
+/// What `systemctl show` says about one timer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimerProperties {
+    pub state: String,
+    pub schedule: Vec<String>,
+}
+
+/// Parses `systemctl show --property=Id,UnitFileState,TimersCalendar,TimersMonotonic`, which
+/// prints one block of `Name=value` lines per unit, separated by empty lines.
+pub fn parse_show(output: &str) -> HashMap<String, TimerProperties> {
+    let mut units = HashMap::new();
+    for block in output.split("\n\n") {
+        let mut id = None;
+        let mut properties = TimerProperties::default();
+        for line in block.lines() {
+            let Some((name, value)) = line.split_once('=') else {
+                continue;
+            };
+            match name {
+                "Id" => id = Some(value.to_string()),
+                "UnitFileState" => properties.state = value.to_string(),
+                "TimersCalendar" | "TimersMonotonic" => {
+                    properties.schedule.extend(parse_trigger(value))
+                }
+                _ => {}
+            }
+        }
+        if let Some(id) = id {
+            units.insert(id, properties);
+        }
+    }
+    units
+}
+
+/// Turns `{ OnCalendar=*-*-* 00:00:00 ; next_elapse=... }` into `OnCalendar=*-*-* 00:00:00`.
+/// Monotonic triggers are shown in microseconds (`OnBootUSec=15min`), and are given the names
+/// they have in unit files instead.
+fn parse_trigger(value: &str) -> Option<String> {
+    let inner = value.trim().strip_prefix('{')?.strip_suffix('}')?;
+    let trigger = inner.split(" ; ").next()?.trim();
+    let (name, spec) = trigger.split_once('=')?;
+    let name = name
+        .strip_suffix("USec")
+        .map_or(name.to_string(), |base| format!("{}Sec", base));
+    Some(format!("{}={}", name, spec))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/systemd")
+            .join(name)
+    }
+
+    #[test]
+    fn test_parse_list_timers() {
+        let output = fs::read_to_string(fixture("list-timers")).unwrap();
+        let timers = parse_list_timers(&output);
+        assert_eq!(
+            timers[0],
+            Timer {
+                unit: "logrotate.timer".to_string(),
+                activates: "logrotate.service".to_string(),
+                next: Some("Sat 2025-03-01 00:00:00 UTC".to_string()),
+                left: Some("5h 17min left".to_string()),
+                last: Some("Fri 2025-02-28 00:00:01 UTC".to_string()),
+                passed: Some("18h ago".to_string()),
+                ..Timer::default()
+            }
+        );
+        assert_eq!(timers[1].left.as_deref(), Some("1 day 8h left"));
+        assert_eq!(timers[1].passed.as_deref(), Some("5 days ago"));
+        assert_eq!(timers[2].left.as_deref(), Some("11h"));
+        assert_eq!(timers[2].last, None);
+        assert_eq!(timers[2].passed, None);
+        assert_eq!(
+            (timers[3].next.clone(), timers[3].left.clone()),
+            (None, None)
+        );
+        assert_eq!(timers[3].last, None);
+        assert_eq!(timers[3].activates, "fstrim.service");
+        assert_eq!(timers[4].next, None);
+        assert_eq!(
+            timers[4].last.as_deref(),
+            Some("Fri 2025-02-28 12:00:00 CET")
+        );
+        assert_eq!(timers[4].passed.as_deref(), Some("6h ago"));
+        assert_eq!(timers.len(), 5);
+
+        assert_eq!(parse_list_timers("\n0 timers listed.\n"), vec![]);
+    }
+
+    #[test]
+    fn test_parse_show() {
+        let output = fs::read_to_string(fixture("show")).unwrap();
+        let units = parse_show(&output);
+        assert_eq!(
+            units["logrotate.timer"],
+            TimerProperties {
+                state: "enabled".to_string(),
+                schedule: vec!["OnCalendar=*-*-* 00:00:00".to_string()],
+            }
+        );
+        assert_eq!(units["fstrim.timer"].state, "disabled");
+        assert_eq!(
+            units["fstrim.timer"].schedule,
+            vec!["OnCalendar=Mon *-*-* 00:00:00".to_string()]
+        );
+        assert_eq!(
+            units["systemd-tmpfiles-clean.timer"],
+            TimerProperties {
+                state: "static".to_string(),
+                schedule: vec![
+                    "OnBootSec=15min".to_string(),
+                    "OnUnitActiveSec=1d".to_string()
+                ],
+            }
+        );
+    }
+}
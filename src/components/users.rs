@@ -1,26 +1,42 @@
+pub mod accounts;
+pub mod groups;
+pub mod logins;
+pub mod shadow;
 pub mod utils;
 
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use chrono::{DateTime, Local, Utc};
 use color_eyre::Result;
 use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
     layout::{Constraint, Layout, Margin, Rect},
     prelude::Frame,
-    text::Text,
+    text::{Line, Text},
     widgets::{
-        Cell, HighlightSpacing, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table,
-        TableState,
+        Block, Borders, Cell, HighlightSpacing, Paragraph, Row, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Table, TableState, Wrap,
     },
 };
 use tokio::sync::mpsc::UnboundedSender;
+use tracing::error;
 
 use super::{
-    users::utils::constraint_len_calculator, users::utils::get_users_from_passwd, Component,
+    form::Form, users::utils::constraint_len_calculator, users::utils::get_users_from_passwd,
+    Component,
 };
 use crate::{
     action::{Action, Module},
     config::Config,
-    draw::Drawable,
-    style::TableStyles,
+    draw::{center, draw_confirmation, Drawable},
+    style::{EditWindowStyles, TableStyles},
 };
+use accounts::NewUser;
+use groups::{group_name, memberships, read_groups, Group};
+use logins::Login;
+use shadow::{read_shadow, Shadow};
 
 impl Drawable for Users {}
 const ITEM_HEIGHT: usize = 3;
@@ -35,11 +51,156 @@ pub struct Users {
     longest_item_lens: (u16, u16, u16),
     scroll_state: ScrollbarState,
     styles: TableStyles,
+    window_styles: EditWindowStyles,
+    /// The form open over the table, if any.
+    editing: Option<(UserForm, Form)>,
+    /// The change waiting for confirmation.
+    change: Option<Change>,
+    /// What the command printed when the confirmed change failed.
+    failure: Option<String>,
+    /// The outcome of the last change.
+    message: Option<String>,
+    groups: Vec<Group>,
+    /// `None` when the shadow file cannot be read, as it can only by root.
+    shadow: Option<Vec<Shadow>>,
+    /// The last login of every user in `wtmp`, read once per load.
+    wtmp: HashMap<String, Login>,
+    /// Sections of label and value pairs about every account, worked out once per load so that
+    /// moving through the table runs no commands.
+    details: Vec<Vec<Section>>,
 }
 
-#[derive(Default)]
+/// A titled list of label and value pairs in the details pane.
+type Section = (&'static str, Vec<(String, String)>);
+
+/// The form open over the table.
+enum UserForm {
+    New,
+    /// The supplementary groups of the user.
+    Groups(String),
+}
+
+/// A change to an account, shown with its commands before they run.
+enum Change {
+    Create(NewUser),
+    Delete {
+        username: String,
+        remove_home: bool,
+    },
+    Lock {
+        username: String,
+        locked: bool,
+    },
+    /// Adds the user to the groups of `add` and removes it from those of `remove`.
+    Memberships {
+        username: String,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+}
+
+impl Change {
+    fn question(&self) -> String {
+        match self {
+            Change::Create(user) => format!("Create the user {}?", user.username),
+            Change::Delete { username, .. } => format!("Delete the user {}?", username),
+            Change::Lock {
+                username,
+                locked: true,
+            } => format!("Lock the password of {}?", username),
+            Change::Lock { username, .. } => format!("Unlock the password of {}?", username),
+            Change::Memberships { username, .. } => {
+                format!("Change the groups of {}?", username)
+            }
+        }
+    }
+
+    fn commands(&self) -> Vec<String> {
+        match self {
+            Change::Create(user) => vec![user.command_line()],
+            Change::Delete {
+                username,
+                remove_home,
+            } => vec![format!(
+                "userdel {}",
+                accounts::delete_args(username, *remove_home).join(" ")
+            )],
+            Change::Lock { username, locked } => vec![format!(
+                "usermod {}",
+                accounts::lock_args(username, *locked).join(" ")
+            )],
+            Change::Memberships {
+                username,
+                add,
+                remove,
+            } => membership_changes(add, remove)
+                .map(|(group, add)| {
+                    format!(
+                        "gpasswd {}",
+                        groups::member_args(group, username, add).join(" ")
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn notes(&self) -> Vec<String> {
+        match self {
+            Change::Delete { remove_home, .. } => vec![format!(
+                "[{}] Also remove the home directory and mail spool",
+                if *remove_home { "x" } else { " " }
+            )],
+            _ => Vec::new(),
+        }
+    }
+
+    fn apply(&self) -> io::Result<String> {
+        match self {
+            Change::Create(user) => accounts::create(user),
+            Change::Delete {
+                username,
+                remove_home,
+            } => accounts::delete(username, *remove_home).map(|()| format!("Deleted {}", username)),
+            Change::Lock { username, locked } => {
+                accounts::set_locked(username, *locked).map(|()| {
+                    if *locked {
+                        format!("Locked {}", username)
+                    } else {
+                        format!("Unlocked {}", username)
+                    }
+                })
+            }
+            Change::Memberships {
+                username,
+                add,
+                remove,
+            } => {
+                for (group, add) in membership_changes(add, remove) {
+                    groups::set_member(group, username, add)?;
+                }
+                Ok(format!("Changed the groups of {}", username))
+            }
+        }
+    }
+}
+
+/// The groups to join and to leave, each with whether it is joined.
+fn membership_changes<'a>(
+    add: &'a [String],
+    remove: &'a [String],
+) -> impl Iterator<Item = (&'a str, bool)> {
+    add.iter()
+        .map(|group| (group.as_str(), true))
+        .chain(remove.iter().map(|group| (group.as_str(), false)))
+}
+
+#[derive(Debug, Default, PartialEq)]
 pub struct User {
     pub username: String,
+    pub uid: u32,
+    pub gid: u32,
+    /// The comment field, usually the full name.
+    pub gecos: String,
     pub docroot: String,
     pub shell: String,
 }
@@ -66,8 +227,299 @@ impl Users {
             longest_item_lens: constraint_len_calculator(&users),
             scroll_state: ScrollbarState::new(scroll_position),
             styles: TableStyles::new(),
+            window_styles: EditWindowStyles::new(),
             items: users,
+            editing: None,
+            change: None,
+            failure: None,
+            message: None,
+            groups: Vec::new(),
+            shadow: None,
+            wtmp: HashMap::new(),
+            details: Vec::new(),
+        }
+    }
+
+    fn load(&mut self) {
+        self.items = get_users_from_passwd(&self.config.settings.users.docroot);
+        let scroll_position = if self.items.is_empty() {
+            0
+        } else {
+            (self.items.len() - 1) * ITEM_HEIGHT
+        };
+        self.scroll_state = ScrollbarState::new(scroll_position);
+        if self.state.selected().is_some_and(|i| i >= self.items.len()) {
+            self.last_row();
         }
+        self.groups = read_groups().unwrap_or_else(|e| {
+            error!("Error reading {}: {}", groups::GROUP, e);
+            Vec::new()
+        });
+        self.shadow = read_shadow().ok();
+        self.wtmp = logins::read_wtmp(Path::new(logins::WTMP)).unwrap_or_else(|e| {
+            error!("Error reading {}: {}", logins::WTMP, e);
+            HashMap::new()
+        });
+        self.details = self
+            .items
+            .iter()
+            .map(|user| {
+                if user.docroot.is_empty() {
+                    Vec::new()
+                } else {
+                    self.user_details(user)
+                }
+            })
+            .collect();
+    }
+
+    /// Collects what there is to know about an account.
+    fn user_details(&self, user: &User) -> Vec<Section> {
+        let pair = |label: &str, value: String| (label.to_string(), value);
+
+        let primary = group_name(&self.groups, user.gid)
+            .map(|name| format!("{} ({})", user.gid, name))
+            .unwrap_or_else(|| user.gid.to_string());
+        let account = vec![
+            pair("UID", user.uid.to_string()),
+            pair("GID", primary),
+            pair("GECOS", user.gecos.clone()),
+            pair("Home", user.docroot.clone()),
+            pair("Shell", user.shell.clone()),
+        ];
+
+        let supplementary = memberships(&self.groups, &user.username);
+        let groups = vec![pair(
+            "Groups",
+            if supplementary.is_empty() {
+                "none".to_string()
+            } else {
+                supplementary.join(", ")
+            },
+        )];
+
+        let entry = self
+            .shadow
+            .as_ref()
+            .and_then(|entries| entries.iter().find(|entry| entry.username == user.username));
+        let password = match entry {
+            Some(entry) => {
+                let today = u64::try_from(Utc::now().timestamp() / 86400).unwrap_or_default();
+                let state = if entry.is_locked() {
+                    "Locked"
+                } else if entry.is_expired(today) {
+                    "Expired"
+                } else {
+                    "Active"
+                };
+                let mut lines = vec![
+                    pair("Account", state.to_string()),
+                    pair("Password", entry.status().to_string()),
+                ];
+                lines.extend(entry.aging());
+                lines
+            }
+            None => {
+                let state = match accounts::is_locked(&user.username) {
+                    Ok(true) => "Locked".to_string(),
+                    Ok(false) => "Active".to_string(),
+                    Err(e) => format!("unknown ({})", e),
+                };
+                let mut lines = vec![pair("Account", state)];
+                match shadow::chage(&user.username) {
+                    Ok(aging) => lines.extend(aging),
+                    Err(e) => lines.push(pair("Aging", format!("unknown ({})", e))),
+                }
+                lines
+            }
+        };
+
+        let last_login = match logins::last_login(&user.username, user.uid, &self.wtmp) {
+            Ok(Some(login)) => {
+                let time = DateTime::from_timestamp(login.time, 0)
+                    .map(|time| {
+                        time.with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string()
+                    })
+                    .unwrap_or_default();
+                let mut text = format!("{} on {}", time, login.line);
+                if !login.host.is_empty() {
+                    text.push_str(&format!(" from {}", login.host));
+                }
+                text
+            }
+            Ok(None) => "never".to_string(),
+            Err(e) => format!("unknown ({})", e),
+        };
+
+        vec![
+            ("Account", account),
+            ("Groups", groups),
+            ("Password", password),
+            ("Last login", vec![pair("Last login", last_login)]),
+        ]
+    }
+
+    fn draw_details(&self, frame: &mut Frame, area: Rect) {
+        let mut lines = Vec::new();
+        let details = self.state.selected().and_then(|i| self.details.get(i));
+        for (title, pairs) in details.into_iter().flatten() {
+            if !lines.is_empty() {
+                lines.push(Line::raw(""));
+            }
+            lines.push(Line::styled(title.to_string(), self.styles.header_style));
+            lines.extend(
+                pairs
+                    .iter()
+                    .map(|(label, value)| Line::raw(format!("{}: {}", label, value))),
+            );
+        }
+        let pane = Paragraph::new(Text::from(lines))
+            .wrap(Wrap { trim: false })
+            .style(self.styles.row_style.bg(self.styles.normal_row_color))
+            .block(Block::default().borders(Borders::LEFT).title(" Details "));
+        frame.render_widget(pane, area);
+    }
+
+    /// The selected account; the placeholder rows for errors and empty lists have no home.
+    fn selected(&self) -> Option<&User> {
+        self.state
+            .selected()
+            .and_then(|i| self.items.get(i))
+            .filter(|user| !user.docroot.is_empty())
+    }
+
+    fn new_user_form(&self) -> Form {
+        let mut form = Form::new(&[
+            ("Username", ""),
+            ("Home directory (empty for the docroot)", ""),
+            ("Shell", "/bin/bash"),
+            ("Primary group (empty for the default)", ""),
+            ("Password (optional)", ""),
+        ]);
+        form.set_masked(4);
+        form
+    }
+
+    fn groups_form(&self, username: &str) -> Form {
+        let current = memberships(&self.groups, username).join(", ");
+        Form::new(&[("Supplementary groups, separated by commas", &current)])
+    }
+
+    /// Turns the form into a change to confirm, or shows what is wrong with it.
+    fn submit_form(&mut self) -> Option<Action> {
+        let change = match self.editing.as_mut()? {
+            (UserForm::New, form) => Self::new_user(form, &self.config.settings.users.docroot),
+            (UserForm::Groups(username), form) => Self::memberships(form, username, &self.groups),
+        }?;
+        self.editing = None;
+        Some(self.confirm(change))
+    }
+
+    fn new_user(form: &mut Form, docroot: &str) -> Option<Change> {
+        let username = form.value(0).trim().to_string();
+        let home = match form.value(1).trim() {
+            "" => format!("{}/{}", docroot.trim_end_matches('/'), username),
+            home => home.to_string(),
+        };
+        let user = NewUser {
+            username,
+            home,
+            shell: form.value(2).trim().to_string(),
+            group: form.value(3).trim().to_string(),
+            password: form.value(4),
+        };
+        if let Err(e) = user.validate() {
+            form.error = Some(e);
+            return None;
+        }
+        Some(Change::Create(user))
+    }
+
+    fn memberships(form: &mut Form, username: &str, groups: &[Group]) -> Option<Change> {
+        let wanted: Vec<String> = form
+            .value(0)
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect();
+        if let Some(unknown) = wanted
+            .iter()
+            .find(|name| !groups.iter().any(|group| &group.name == *name))
+        {
+            form.error = Some(format!("There is no group called {}", unknown));
+            return None;
+        }
+        let current = memberships(groups, username);
+        let add: Vec<String> = wanted
+            .iter()
+            .filter(|name| !current.contains(&name.as_str()))
+            .cloned()
+            .collect();
+        let remove: Vec<String> = current
+            .iter()
+            .filter(|name| !wanted.iter().any(|wanted| wanted == *name))
+            .map(|name| name.to_string())
+            .collect();
+        if add.is_empty() && remove.is_empty() {
+            form.error = Some("The groups are the same as before".to_string());
+            return None;
+        }
+        Some(Change::Memberships {
+            username: username.to_string(),
+            add,
+            remove,
+        })
+    }
+
+    fn confirm(&mut self, change: Change) -> Action {
+        self.change = Some(change);
+        self.failure = None;
+        Action::ChangeMode(Module::UsersConfirm)
+    }
+
+    fn ask_lock(&mut self) -> Option<Action> {
+        let username = self.selected()?.username.clone();
+        match accounts::is_locked(&username) {
+            Ok(locked) => Some(self.confirm(Change::Lock {
+                username,
+                locked: !locked,
+            })),
+            Err(e) => {
+                error!("Error reading the password status of {}: {}", username, e);
+                self.message = Some(format!("Error: {}", e));
+                None
+            }
+        }
+    }
+
+    fn apply_change(&mut self) -> Option<Action> {
+        let change = self.change.as_ref()?;
+        match change.apply() {
+            Ok(message) => {
+                self.message = Some(message);
+                self.change = None;
+                Some(Action::ChangeMode(Module::Users))
+            }
+            Err(e) => {
+                error!("Error changing an account: {}", e);
+                self.failure = Some(e.to_string());
+                None
+            }
+        }
+    }
+
+    fn draw_title(&self, frame: &mut Frame, area: Rect) {
+        let mut title = format!(
+            " Users under {}",
+            self.config.settings.users.docroot.trim_end_matches('/')
+        );
+        if let Some(message) = &self.message {
+            title.push_str(&format!("  ·  {}", message));
+        }
+        frame.render_widget(Line::from(title).style(self.styles.header_style), area);
     }
 
     fn draw_table(&mut self, frame: &mut Frame, area: Rect) {
@@ -110,7 +562,7 @@ impl Users {
         .style(
             self.styles
                 .row_style
-                .bg(if (self.items.len() + 1) % 2 == 0 {
+                .bg(if (self.items.len() + 1).is_multiple_of(2) {
                     self.styles.alt_row_color
                 } else {
                     self.styles.normal_row_color
@@ -194,53 +646,158 @@ impl Component for Users {
         Ok(())
     }
 
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if let Some((_, form)) = self.editing.as_mut() {
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Tab => {}
+                _ => form.input(key),
+            }
+        }
+        Ok(None)
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         if let Action::ChangeMode(Module::Users) = action {
-            self.items = get_users_from_passwd(&self.config.settings.users.docroot);
-            let scroll_position = if self.items.is_empty() {
-                0
-            } else {
-                (self.items.len() - 1) * ITEM_HEIGHT
-            };
-            self.scroll_state = ScrollbarState::new(scroll_position);
+            if !self.enabled {
+                self.message = None;
+            }
+            self.editing = None;
+            self.change = None;
+            self.load();
             self.enabled = true;
         }
-        if self.enabled {
+        if !self.enabled {
+            return Ok(None);
+        }
+        if let Some((_, form)) = self.editing.as_mut() {
             match action {
-                Action::ChangeMode(Module::Home) => {
-                    self.enabled = false;
-                    return Ok(Some(Action::ClearScreen));
-                }
-                Action::MoveUp => {
-                    self.previous_row();
-                }
-                Action::MoveDown => {
-                    self.next_row();
+                Action::SwitchElement => form.next_field(),
+                Action::Confirm => return Ok(self.submit_form()),
+                _ => {}
+            }
+            return Ok(None);
+        }
+        if let Some(change) = self.change.as_mut() {
+            match action {
+                Action::ToggleRecord => {
+                    if let Change::Delete { remove_home, .. } = change {
+                        *remove_home = !*remove_home;
+                    }
                 }
-                Action::MoveToTheFirst => {
-                    self.first_row();
+                Action::Confirm => return Ok(self.apply_change()),
+                _ => {}
+            }
+            return Ok(None);
+        }
+        match action {
+            Action::ChangeMode(Module::Home) => {
+                self.enabled = false;
+                return Ok(Some(Action::ClearScreen));
+            }
+            Action::NewRecord => {
+                self.editing = Some((UserForm::New, self.new_user_form()));
+                return Ok(Some(Action::ChangeMode(Module::UsersEdit)));
+            }
+            Action::EditGroups => {
+                if let Some(username) = self.selected().map(|user| user.username.clone()) {
+                    let form = self.groups_form(&username);
+                    self.editing = Some((UserForm::Groups(username), form));
+                    return Ok(Some(Action::ChangeMode(Module::UsersEdit)));
                 }
-                Action::MoveToTheLast => {
-                    self.last_row();
+            }
+            Action::DeleteRecord => {
+                if let Some(user) = self.selected() {
+                    let change = Change::Delete {
+                        username: user.username.clone(),
+                        remove_home: false,
+                    };
+                    return Ok(Some(self.confirm(change)));
                 }
-                _ => {}
             }
+            Action::ToggleRecord => return Ok(self.ask_lock()),
+            Action::MoveUp => {
+                self.previous_row();
+            }
+            Action::MoveDown => {
+                self.next_row();
+            }
+            Action::MoveToTheFirst => {
+                self.first_row();
+            }
+            Action::MoveToTheLast => {
+                self.last_row();
+            }
+            _ => {}
         }
         Ok(None)
     }
 
     fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
         if self.enabled {
-            let vertical = Layout::vertical([Constraint::Min(1), Constraint::Length(2)]);
+            let vertical = Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(2),
+            ]);
             let rects = vertical.split(area);
 
-            self.draw_table(frame, rects[0]);
-            self.draw_scrollbar(frame, rects[0]);
-            self.draw_footer(
-                frame,
-                rects[1],
-                vec![("<Esc>", "Quit"), ("<↓↑>", "Move up and down")],
-            )?;
+            let [table, details] =
+                Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                    .areas(rects[1]);
+
+            self.draw_title(frame, rects[0]);
+            self.draw_table(frame, table);
+            self.draw_scrollbar(frame, table);
+            self.draw_details(frame, details);
+
+            if let Some((kind, form)) = self.editing.as_mut() {
+                let title = match kind {
+                    UserForm::New => " New user ".to_string(),
+                    UserForm::Groups(username) => format!(" Groups of {} ", username),
+                };
+                let popup = center(
+                    frame.area(),
+                    Constraint::Percentage(60),
+                    Constraint::Length(form.height()),
+                );
+                form.draw(frame, popup, &title);
+                self.draw_footer(
+                    frame,
+                    rects[2],
+                    vec![
+                        ("<Esc>", "Close"),
+                        ("<Tab>", "Next field"),
+                        ("<Enter>", "Continue"),
+                    ],
+                )?;
+            } else if let Some(change) = &self.change {
+                draw_confirmation(
+                    frame,
+                    &change.question(),
+                    &change.commands(),
+                    &change.notes(),
+                    self.failure.as_deref(),
+                    &self.window_styles,
+                );
+                let mut keybinds = vec![("<Esc>", "Cancel"), ("<y>", "Yes, run it")];
+                if let Change::Delete { .. } = change {
+                    keybinds.push(("<Space>", "Toggle removing the home directory"));
+                }
+                self.draw_footer(frame, rects[2], keybinds)?;
+            } else {
+                self.draw_footer(
+                    frame,
+                    rects[2],
+                    vec![
+                        ("<Esc>", "Quit"),
+                        ("<↓↑>", "Move up and down"),
+                        ("<n>", "New user"),
+                        ("<d>", "Delete"),
+                        ("<l>", "Lock/unlock"),
+                        ("<g>", "Groups"),
+                    ],
+                )?;
+            }
         }
         Ok(())
     }
@@ -0,0 +1,201 @@
+use std::io;
+
+use crate::command::run;
+
+/// What `useradd` needs to create an account.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NewUser {
+    pub username: String,
+    pub home: String,
+    pub shell: String,
+    /// Primary group; empty lets `useradd` pick one.
+    pub group: String,
+    /// Set with `chpasswd` once the account exists; empty leaves it without a password.
+    pub password: String,
+}
+
+impl NewUser {
+    /// Checks the name the way `useradd` does by default.
+    pub fn validate(&self) -> Result<(), String> {
+        let name = self.username.as_str();
+        let valid = !name.is_empty()
+            && name.len() <= 32
+            && name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+            && name
+                .trim_end_matches('$')
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+        if !valid {
+            return Err(
+                "The username is lowercase letters, digits, _ and -, starting with a letter"
+                    .to_string(),
+            );
+        }
+        if !self.home.starts_with('/') {
+            return Err("The home directory is an absolute path".to_string());
+        }
+        if !self.shell.starts_with('/') {
+            return Err("The shell is an absolute path".to_string());
+        }
+        if self.password.contains([':', '\n']) {
+            return Err("The password cannot contain : or line breaks".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn useradd_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--create-home".to_string(),
+            "--home-dir".to_string(),
+            self.home.clone(),
+            "--shell".to_string(),
+            self.shell.clone(),
+        ];
+        if !self.group.is_empty() {
+            args.extend(["--gid".to_string(), self.group.clone()]);
+        }
+        args.extend(["--".to_string(), self.username.clone()]);
+        args
+    }
+
+    /// The commands creating the account, as shown before they run.
+    pub fn command_line(&self) -> String {
+        let mut line = format!("useradd {}", self.useradd_args().join(" "));
+        if !self.password.is_empty() {
+            line.push_str(&format!(" && echo '{}:********' | chpasswd", self.username));
+        }
+        line
+    }
+}
+
+/// Creates the account and tells how it went; an account whose password could not be set
+/// still exists, which the report says.
+pub fn create(user: &NewUser) -> io::Result<String> {
+    let args = user.useradd_args();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    run("useradd", &args, None)?;
+    if !user.password.is_empty() {
+        let input = format!("{}:{}", user.username, user.password);
+        if let Err(e) = run("chpasswd", &[], Some(&input)) {
+            return Ok(format!(
+                "Created {}, but setting the password failed: {}",
+                user.username, e
+            ));
+        }
+    }
+    Ok(format!("Created {}", user.username))
+}
+
+pub fn delete_args(username: &str, remove_home: bool) -> Vec<&str> {
+    let mut args = Vec::new();
+    if remove_home {
+        args.push("--remove");
+    }
+    args.extend(["--", username]);
+    args
+}
+
+pub fn delete(username: &str, remove_home: bool) -> io::Result<()> {
+    run("userdel", &delete_args(username, remove_home), None).map(|_| ())
+}
+
+pub fn lock_args(username: &str, locked: bool) -> Vec<&str> {
+    vec![if locked { "-L" } else { "-U" }, "--", username]
+}
+
+/// Locks or unlocks the password of the account with `usermod`.
+pub fn set_locked(username: &str, locked: bool) -> io::Result<()> {
+    run("usermod", &lock_args(username, locked), None).map(|_| ())
+}
+
+pub fn is_locked(username: &str) -> io::Result<bool> {
+    let output = run("passwd", &["-S", "--", username], None)?;
+    parse_passwd_status(&output)
+        .ok_or_else(|| io::Error::other(format!("Unexpected passwd output: {}", output.trim())))
+}
+
+/// Whether `passwd -S` reports the password as locked: its second column is `L` (or `LK`) for
+/// locked, `P` (or `PS`) for usable and `NP` for none.
+pub fn parse_passwd_status(output: &str) -> Option<bool> {
+    let status = output.split_whitespace().nth(1)?;
+    Some(status.starts_with('L'))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn user() -> NewUser {
+        NewUser {
+            username: "shop".to_string(),
+            home: "/home/shop".to_string(),
+            shell: "/bin/bash".to_string(),
+            group: "www-data".to_string(),
+            password: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_useradd_args() {
+        assert_eq!(
+            user().command_line(),
+            "useradd --create-home --home-dir /home/shop --shell /bin/bash --gid www-data -- shop"
+        );
+        let with_password = NewUser {
+            group: String::new(),
+            password: "secret".to_string(),
+            ..user()
+        };
+        assert_eq!(
+            with_password.command_line(),
+            "useradd --create-home --home-dir /home/shop --shell /bin/bash -- shop \
+             && echo 'shop:********' | chpasswd"
+        );
+        assert_eq!(delete_args("shop", true), ["--remove", "--", "shop"]);
+        assert_eq!(lock_args("shop", false), ["-U", "--", "shop"]);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(user().validate(), Ok(()));
+        for username in ["", "Shop", "1shop", "shop site", "-shop"] {
+            let user = NewUser {
+                username: username.to_string(),
+                ..user()
+            };
+            assert!(user.validate().is_err(), "{}", username);
+        }
+        assert!(NewUser {
+            home: "home/shop".to_string(),
+            ..user()
+        }
+        .validate()
+        .is_err());
+        assert!(NewUser {
+            password: "a:b".to_string(),
+            ..user()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_passwd_status() {
+        assert_eq!(
+            parse_passwd_status("shop L 03/01/2025 0 99999 7 -1\n"),
+            Some(true)
+        );
+        assert_eq!(
+            parse_passwd_status("shop LK 2025-03-01 0 99999 7 -1 (Password locked.)"),
+            Some(true)
+        );
+        assert_eq!(
+            parse_passwd_status("shop P 2025-03-01 0 99999 7 -1"),
+            Some(false)
+        );
+        assert_eq!(parse_passwd_status("shop NP 2025-03-01"), Some(false));
+        assert_eq!(parse_passwd_status(""), None);
+    }
+}
@@ -0,0 +1,210 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::command::run;
+
+pub const GROUP: &str = "/etc/group";
+pub const GSHADOW: &str = "/etc/gshadow";
+
+/// An entry of `/etc/group`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Group {
+    pub name: String,
+    pub gid: u32,
+    /// Users that have the group as a supplementary group.
+    pub members: Vec<String>,
+}
+
+pub fn parse_group(content: &str) -> Vec<Group> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split(':').collect();
+            if parts.len() < 4 || line.starts_with('#') {
+                return None;
+            }
+            Some(Group {
+                name: parts[0].to_string(),
+                gid: parts[2].parse().ok()?,
+                members: split_members(parts[3]),
+            })
+        })
+        .collect()
+}
+
+pub fn read_groups() -> io::Result<Vec<Group>> {
+    Ok(parse_group(&fs::read_to_string(Path::new(GROUP))?))
+}
+
+/// An entry of `/etc/gshadow`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupShadow {
+    pub name: String,
+    /// The password hash; `!` or `*` when the group has no password.
+    pub password: String,
+    /// Users that may change the group's password and members with `gpasswd`.
+    pub admins: Vec<String>,
+    pub members: Vec<String>,
+}
+
+impl GroupShadow {
+    pub fn has_password(&self) -> bool {
+        !self.password.is_empty() && !self.password.starts_with(['!', '*'])
+    }
+}
+
+pub fn parse_gshadow(content: &str) -> Vec<GroupShadow> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split(':').collect();
+            if parts.len() < 4 || line.starts_with('#') {
+                return None;
+            }
+            Some(GroupShadow {
+                name: parts[0].to_string(),
+                password: parts[1].to_string(),
+                admins: split_members(parts[2]),
+                members: split_members(parts[3]),
+            })
+        })
+        .collect()
+}
+
+/// Reads `/etc/gshadow`, which only root can.
+pub fn read_gshadow() -> io::Result<Vec<GroupShadow>> {
+    Ok(parse_gshadow(&fs::read_to_string(Path::new(GSHADOW))?))
+}
+
+/// Checks a group name the way `groupadd` does by default.
+pub fn validate_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.len() <= 32
+        && name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} is not a valid name: lowercase letters, digits, _ and -, starting with a letter",
+            name
+        ))
+    }
+}
+
+/// The arguments of `groupadd`; without a GID it picks the next free one.
+pub fn groupadd_args<'a>(name: &'a str, gid: Option<&'a str>) -> Vec<&'a str> {
+    let mut args = Vec::new();
+    if let Some(gid) = gid {
+        args.extend(["--gid", gid]);
+    }
+    args.extend(["--", name]);
+    args
+}
+
+pub fn create(name: &str, gid: Option<&str>) -> io::Result<()> {
+    run("groupadd", &groupadd_args(name, gid), None).map(|_| ())
+}
+
+pub fn delete(name: &str) -> io::Result<()> {
+    run("groupdel", &["--", name], None).map(|_| ())
+}
+
+/// The arguments of `gpasswd` adding `user` to `group`, or removing it.
+pub fn member_args<'a>(group: &'a str, user: &'a str, add: bool) -> Vec<&'a str> {
+    vec![if add { "-a" } else { "-d" }, user, "--", group]
+}
+
+pub fn set_member(group: &str, user: &str, add: bool) -> io::Result<()> {
+    run("gpasswd", &member_args(group, user, add), None).map(|_| ())
+}
+
+/// The name of the group with `gid`.
+pub fn group_name(groups: &[Group], gid: u32) -> Option<&str> {
+    groups
+        .iter()
+        .find(|group| group.gid == gid)
+        .map(|group| group.name.as_str())
+}
+
+/// The supplementary groups of `username`.
+pub fn memberships<'a>(groups: &'a [Group], username: &str) -> Vec<&'a str> {
+    groups
+        .iter()
+        .filter(|group| group.members.iter().any(|member| member == username))
+        .map(|group| group.name.as_str())
+        .collect()
+}
+
+fn split_members(members: &str) -> Vec<String> {
+    members
+        .split(',')
+        .map(str::trim)
+        .filter(|member| !member.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_gshadow() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/users/gshadow");
+        let groups = parse_gshadow(&fs::read_to_string(path).unwrap());
+        assert_eq!(
+            groups[1],
+            GroupShadow {
+                name: "www-data".to_string(),
+                password: "!".to_string(),
+                admins: vec!["deploy".to_string()],
+                members: vec!["shop".to_string(), "deploy".to_string()],
+            }
+        );
+        assert!(!groups[1].has_password());
+        assert!(groups[4].has_password());
+        assert_eq!(groups.len(), 5);
+    }
+
+    #[test]
+    fn test_commands() {
+        assert_eq!(groupadd_args("sites", None), ["--", "sites"]);
+        assert_eq!(
+            groupadd_args("sites", Some("2000")),
+            ["--gid", "2000", "--", "sites"]
+        );
+        assert_eq!(
+            member_args("www-data", "shop", true),
+            ["-a", "shop", "--", "www-data"]
+        );
+        assert_eq!(member_args("www-data", "shop", false)[0], "-d");
+        assert_eq!(validate_name("www-data"), Ok(()));
+        assert!(validate_name("Web").is_err());
+        assert!(validate_name("").is_err());
+    }
+
+    #[test]
+    fn test_parse_group() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/users/group");
+        let groups = parse_group(&fs::read_to_string(path).unwrap());
+        assert_eq!(
+            groups[1],
+            Group {
+                name: "www-data".to_string(),
+                gid: 33,
+                members: vec!["shop".to_string(), "deploy".to_string()],
+            }
+        );
+        assert_eq!(groups[0].members, Vec::<String>::new());
+        assert_eq!(group_name(&groups, 1001), Some("shop"));
+        assert_eq!(memberships(&groups, "shop"), ["www-data", "sudo"]);
+        assert_eq!(memberships(&groups, "blog"), ["deploy"]);
+    }
+}
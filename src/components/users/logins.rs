@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+pub const WTMP: &str = "/var/log/wtmp";
+pub const LASTLOG: &str = "/var/log/lastlog";
+
+/// The size of a `struct utmp` record on Linux.
+const UTMP_SIZE: usize = 384;
+/// `ut_type` of a login.
+const USER_PROCESS: i16 = 7;
+/// The size of a `struct lastlog` record, one per UID.
+const LASTLOG_SIZE: usize = 292;
+
+/// When and where a user last logged in.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Login {
+    /// Seconds since 1970-01-01 UTC.
+    pub time: i64,
+    /// The terminal, e.g. "pts/0".
+    pub line: String,
+    /// The remote host, empty for local logins.
+    pub host: String,
+}
+
+/// The last login of every user recorded in a `wtmp` file.
+pub fn parse_wtmp(data: &[u8]) -> HashMap<String, Login> {
+    let mut logins: HashMap<String, Login> = HashMap::new();
+    for record in data.chunks_exact(UTMP_SIZE) {
+        if i16::from_le_bytes([record[0], record[1]]) != USER_PROCESS {
+            continue;
+        }
+        let user = text(&record[44..76]);
+        let login = Login {
+            time: i64::from(i32_at(record, 340)),
+            line: text(&record[8..40]),
+            host: text(&record[76..332]),
+        };
+        if logins.get(&user).is_none_or(|last| last.time <= login.time) {
+            logins.insert(user, login);
+        }
+    }
+    logins
+}
+
+/// Reads the last login of every user from a `wtmp` file.
+pub fn read_wtmp(path: &Path) -> io::Result<HashMap<String, Login>> {
+    Ok(parse_wtmp(&fs::read(path)?))
+}
+
+/// The last login of `uid` from a `lastlog` file, which holds a record per UID.
+///
+/// Only that record is read: the file is sparse and as large as the highest UID needs, which
+/// runs to hundreds of gigabytes with UIDs like 4294967294.
+pub fn read_lastlog(path: &Path, uid: u32) -> io::Result<Option<Login>> {
+    let mut file = File::open(path)?;
+    let mut record = [0; LASTLOG_SIZE];
+    file.seek(SeekFrom::Start(u64::from(uid) * LASTLOG_SIZE as u64))?;
+    match file.read_exact(&mut record) {
+        Ok(()) => Ok(parse_lastlog(&record)),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// A `lastlog` record, `None` when the user never logged in.
+fn parse_lastlog(record: &[u8; LASTLOG_SIZE]) -> Option<Login> {
+    let time = i64::from(i32_at(record, 0));
+    if time == 0 {
+        return None;
+    }
+    Some(Login {
+        time,
+        line: text(&record[4..36]),
+        host: text(&record[36..292]),
+    })
+}
+
+/// The last login of the user, from `lastlog` or else from the logins read from `wtmp`.
+pub fn last_login(
+    username: &str,
+    uid: u32,
+    wtmp: &HashMap<String, Login>,
+) -> io::Result<Option<Login>> {
+    match read_lastlog(Path::new(LASTLOG), uid) {
+        Ok(Some(login)) => Ok(Some(login)),
+        Ok(None) => Ok(wtmp.get(username).cloned()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(wtmp.get(username).cloned()),
+        Err(e) => Err(e),
+    }
+}
+
+fn i32_at(record: &[u8], offset: usize) -> i32 {
+    i32::from_le_bytes([
+        record[offset],
+        record[offset + 1],
+        record[offset + 2],
+        record[offset + 3],
+    ])
+}
+
+/// A NUL padded string field.
+fn text(field: &[u8]) -> String {
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/users")
+            .join(name)
+    }
+
+    #[test]
+    fn test_parse_wtmp() {
+        let logins = read_wtmp(&fixture("wtmp")).unwrap();
+        assert_eq!(
+            logins["shop"],
+            Login {
+                time: 1741000000,
+                line: "pts/1".to_string(),
+                host: "198.51.100.7".to_string(),
+            }
+        );
+        assert_eq!(logins["root"].line, "tty1");
+        // Boot records and logouts are not logins.
+        assert_eq!(logins.len(), 2);
+    }
+
+    #[test]
+    fn test_read_lastlog() {
+        let path = fixture("lastlog");
+        assert_eq!(
+            read_lastlog(&path, 0).unwrap(),
+            Some(Login {
+                time: 1740900000,
+                line: "tty1".to_string(),
+                host: String::new(),
+            })
+        );
+        assert_eq!(read_lastlog(&path, 1).unwrap(), None);
+        assert_eq!(
+            read_lastlog(&path, 2).unwrap().unwrap().host,
+            "198.51.100.7"
+        );
+        assert_eq!(read_lastlog(&path, 1001).unwrap(), None);
+        // Far past the end of the file, as for nfsnobody.
+        assert_eq!(read_lastlog(&path, 4294967294).unwrap(), None);
+    }
+}
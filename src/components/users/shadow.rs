@@ -0,0 +1,215 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use chrono::{Days, NaiveDate};
+
+pub const SHADOW: &str = "/etc/shadow";
+
+/// An entry of `/etc/shadow`. The dates are days since 1970-01-01.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Shadow {
+    pub username: String,
+    /// The password hash, behind a `!` while the password is locked.
+    pub password: String,
+    pub last_change: Option<u64>,
+    pub min_days: Option<u64>,
+    pub max_days: Option<u64>,
+    pub warn_days: Option<u64>,
+    pub inactive_days: Option<u64>,
+    pub expire: Option<u64>,
+}
+
+impl Shadow {
+    pub fn is_locked(&self) -> bool {
+        self.password.starts_with('!')
+    }
+
+    /// Whether the account expired by `today`, in days since 1970-01-01.
+    pub fn is_expired(&self, today: u64) -> bool {
+        self.expire.is_some_and(|expire| expire <= today)
+    }
+
+    pub fn status(&self) -> &'static str {
+        match self.password.as_str() {
+            password if password.starts_with('!') => "Locked",
+            "" => "No password",
+            "*" => "No password login",
+            _ => "Set",
+        }
+    }
+
+    /// The password aging, with the labels and wording `chage -l` uses.
+    pub fn aging(&self) -> Vec<(String, String)> {
+        let must_change = self.last_change == Some(0);
+        let expires = match (self.last_change, self.max_days) {
+            (Some(last_change), Some(max_days)) if !must_change && max_days < NO_MAXIMUM => {
+                Some(last_change + max_days)
+            }
+            _ => None,
+        };
+        let must_change_or = |date: Option<u64>| {
+            if must_change {
+                "password must be changed".to_string()
+            } else {
+                format_days(date)
+            }
+        };
+        let days = |days: Option<u64>| days.map_or("-1".to_string(), |days| days.to_string());
+        [
+            ("Last password change", must_change_or(self.last_change)),
+            ("Password expires", must_change_or(expires)),
+            (
+                "Password inactive",
+                must_change_or(expires.zip(self.inactive_days).map(|(at, days)| at + days)),
+            ),
+            ("Account expires", format_days(self.expire)),
+            (
+                "Minimum number of days between password change",
+                days(self.min_days),
+            ),
+            (
+                "Maximum number of days between password change",
+                days(self.max_days),
+            ),
+            (
+                "Number of days of warning before password expires",
+                days(self.warn_days),
+            ),
+        ]
+        .into_iter()
+        .map(|(label, value)| (label.to_string(), value))
+        .collect()
+    }
+}
+
+/// The maximum age `chage` treats as no maximum at all.
+const NO_MAXIMUM: u64 = 99999;
+
+pub fn parse_shadow(content: &str) -> Vec<Shadow> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split(':').collect();
+            if parts.len() < 8 {
+                return None;
+            }
+            let number = |field: &str| field.parse().ok();
+            Some(Shadow {
+                username: parts[0].to_string(),
+                password: parts[1].to_string(),
+                last_change: number(parts[2]),
+                min_days: number(parts[3]),
+                max_days: number(parts[4]),
+                warn_days: number(parts[5]),
+                inactive_days: number(parts[6]),
+                expire: number(parts[7]),
+            })
+        })
+        .collect()
+}
+
+/// Reads `/etc/shadow`, which only root can.
+pub fn read_shadow() -> io::Result<Vec<Shadow>> {
+    Ok(parse_shadow(&fs::read_to_string(Path::new(SHADOW))?))
+}
+
+/// The password aging of `username` from `chage -l`, for when the shadow file is not readable.
+pub fn chage(username: &str) -> io::Result<Vec<(String, String)>> {
+    let output = Command::new("chage")
+        .args(["-l", "--", username])
+        .env("LC_ALL", "C")
+        .stdin(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "chage failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(parse_chage(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parses the `Label : value` lines of `chage -l`.
+pub fn parse_chage(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (label, value) = line.split_once(':')?;
+            Some((label.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// A date given in days since 1970-01-01, the way `chage` prints it.
+fn format_days(days: Option<u64>) -> String {
+    days.and_then(|days| NaiveDate::from_ymd_opt(1970, 1, 1)?.checked_add_days(Days::new(days)))
+        .map_or("never".to_string(), |date| {
+            date.format("%b %d, %Y").to_string()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn fixture(name: &str) -> String {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/users")
+            .join(name);
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn test_parse_shadow() {
+        let entries = parse_shadow(&fixture("shadow"));
+        assert_eq!(entries.len(), 3);
+        let shop = &entries[1];
+        assert_eq!(shop.username, "shop");
+        assert_eq!(shop.status(), "Set");
+        assert_eq!(shop.inactive_days, Some(14));
+        assert!(!shop.is_expired(20499));
+        assert!(shop.is_expired(20500));
+        assert_eq!(
+            shop.aging(),
+            [
+                ("Last password change", "Oct 04, 2024"),
+                ("Password expires", "Jan 02, 2025"),
+                ("Password inactive", "Jan 16, 2025"),
+                ("Account expires", "Feb 16, 2026"),
+                ("Minimum number of days between password change", "1"),
+                ("Maximum number of days between password change", "90"),
+                ("Number of days of warning before password expires", "7"),
+            ]
+            .map(|(label, value)| (label.to_string(), value.to_string()))
+        );
+
+        let blog = &entries[2];
+        assert!(blog.is_locked());
+        assert_eq!(blog.status(), "Locked");
+        assert_eq!(blog.aging()[1].1, "password must be changed");
+        assert_eq!(entries[0].status(), "No password login");
+        assert_eq!(entries[0].aging()[1].1, "never");
+    }
+
+    #[test]
+    fn test_parse_chage() {
+        let aging = parse_chage(&fixture("chage"));
+        assert_eq!(
+            aging[0],
+            (
+                "Last password change".to_string(),
+                "Oct 04, 2024".to_string()
+            )
+        );
+        assert_eq!(aging[2].1, "never");
+        assert_eq!(aging.len(), 7);
+        // The same labels and dates as the shadow entry the fixture was taken from.
+        let shop = &parse_shadow(&fixture("shadow"))[1];
+        assert_eq!(aging[..2], shop.aging()[..2]);
+    }
+}
@@ -1,69 +1,51 @@
-use std::fs::File;
-use std::io::{self, BufRead};
+use std::fs;
 use std::path::Path;
 use unicode_width::UnicodeWidthStr;
 
 use crate::components::users::User;
 
 pub fn get_users_from_passwd(doc_root_prefix: &str) -> Vec<User> {
-    let path = Path::new("/etc/passwd");
-    let file = File::open(path);
-
-    let file = match file {
-        Ok(f) => f,
+    let content = match fs::read_to_string(Path::new("/etc/passwd")) {
+        Ok(content) => content,
         Err(e) => {
             return vec![User {
                 username: format!("Error: {}", e),
-                docroot: String::new(),
-                shell: String::new(),
+                ..User::default()
             }];
         }
     };
 
-    let reader = io::BufReader::new(file);
-    let mut users = Vec::new();
-
-    for line in reader.lines() {
-        let line = match line {
-            Ok(l) => l,
-            Err(e) => {
-                return vec![User {
-                    username: format!("Error reading line: {}", e),
-                    docroot: String::new(),
-                    shell: String::new(),
-                }];
-            }
-        };
-
-        let parts: Vec<&str> = line.split(':').collect();
-        if parts.len() < 7 {
-            continue;
-        }
-
-        let username = parts[0];
-        let home_dir = parts[5];
-        let shell = parts[6];
-
-        if home_dir.starts_with(doc_root_prefix) {
-            users.push(User {
-                username: username.to_string(),
-                docroot: home_dir.to_string(),
-                shell: shell.to_string(),
-            });
-        }
-    }
-
+    let users = parse_passwd(&content, doc_root_prefix);
     if users.is_empty() {
-        users.push(User {
+        return vec![User {
             username: "No users found".to_string(),
-            docroot: String::new(),
-            shell: String::new(),
-        });
+            ..User::default()
+        }];
     }
-
     users
 }
 
+/// The accounts of a passwd file whose home is under `doc_root_prefix`.
+pub fn parse_passwd(content: &str, doc_root_prefix: &str) -> Vec<User> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split(':').collect();
+            if parts.len() < 7 || !parts[5].starts_with(doc_root_prefix) {
+                return None;
+            }
+            Some(User {
+                username: parts[0].to_string(),
+                uid: parts[2].parse().ok()?,
+                gid: parts[3].parse().ok()?,
+                gecos: parts[4].to_string(),
+                docroot: parts[5].to_string(),
+                shell: parts[6].to_string(),
+            })
+        })
+        .collect()
+}
+
 pub fn constraint_len_calculator(items: &[User]) -> (u16, u16, u16) {
     let username_len = items
         .iter()
@@ -89,3 +71,31 @@ pub fn constraint_len_calculator(items: &[User]) -> (u16, u16, u16) {
     #[allow(clippy::cast_possible_truncation)]
     (username_len as u16, docroot_len as u16, shell_len as u16)
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_passwd() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/users/passwd");
+        let users = parse_passwd(&fs::read_to_string(path).unwrap(), "/home");
+        assert_eq!(
+            users[0],
+            User {
+                username: "shop".to_string(),
+                uid: 1001,
+                gid: 1001,
+                gecos: "Shop Site,,,".to_string(),
+                docroot: "/home/shop".to_string(),
+                shell: "/bin/bash".to_string(),
+            }
+        );
+        assert_eq!(users[1].username, "blog");
+        assert_eq!(users[1].gid, 33);
+        assert_eq!(users.len(), 2);
+    }
+}
@@ -29,12 +29,67 @@ pub struct Settings {
     pub cron: CronSettings,
     #[serde(default)]
     pub users: UserSettings,
+    #[serde(default)]
+    pub ai: AiSettings,
 }
 
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct CronSettings {
     #[serde(default)]
     pub timezone: String,
+    /// Parse and accept a leading seconds field, as some cron implementations do.
+    #[serde(default)]
+    pub seconds: bool,
+    /// How many backups to keep per crontab; 0 turns backups off.
+    #[serde(default = "default_backups")]
+    pub backups: usize,
+    /// Show a diff of every change and ask before writing it to the crontab.
+    #[serde(default)]
+    pub review: bool,
+    /// Where the lock files of jobs wrapped with `flock` go. Must not be writable by everyone.
+    #[serde(default = "default_lock_dir")]
+    pub lock_dir: String,
+    /// Where jobs that log their output write it. Must not be writable by everyone.
+    #[serde(default = "default_log_dir")]
+    pub log_dir: String,
+}
+
+impl Default for CronSettings {
+    fn default() -> Self {
+        Self {
+            timezone: String::new(),
+            seconds: false,
+            backups: default_backups(),
+            review: false,
+            lock_dir: default_lock_dir(),
+            log_dir: default_log_dir(),
+        }
+    }
+}
+
+fn default_backups() -> usize {
+    20
+}
+
+fn default_lock_dir() -> String {
+    get_state_dir().join("locks").to_string_lossy().to_string()
+}
+
+fn default_log_dir() -> String {
+    get_state_dir().join("logs").to_string_lossy().to_string()
+}
+
+/// An OpenAI-compatible chat-completions endpoint used by the cron "Ask AI" field.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct AiSettings {
+    /// E.g. `https://api.openai.com/v1/chat/completions`. Empty uses the offline converter.
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub model: String,
+    /// Name of the environment variable holding the API key, if the endpoint needs one.
+    #[serde(default)]
+    pub api_key_env: String,
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -140,6 +195,13 @@ pub fn get_config_dir() -> PathBuf {
     directory
 }
 
+/// `$XDG_STATE_HOME/tuixel`, or the data directory where there is no such thing.
+pub fn get_state_dir() -> PathBuf {
+    project_directory()
+        .and_then(|proj_dirs| proj_dirs.state_dir().map(PathBuf::from))
+        .unwrap_or_else(get_data_dir)
+}
+
 fn project_directory() -> Option<ProjectDirs> {
     ProjectDirs::from("com", "kdheepak", env!("CARGO_PKG_NAME"))
 }
@@ -1,11 +1,97 @@
 use color_eyre::Result;
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Flex, Layout, Margin, Rect},
     prelude::Frame,
     style::{Color, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Paragraph},
+    widgets::{
+        Block, BorderType, Borders, Cell, Clear, HighlightSpacing, Paragraph, Row, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Table, TableState, Wrap,
+    },
 };
+use unicode_width::UnicodeWidthStr;
+
+use crate::style::{EditWindowStyles, TableStyles};
+
+/// Height of a table row, which shows its values between two empty lines.
+pub const RECORD_HEIGHT: usize = 3;
+
+/// A row of a table drawn with [`draw_records`].
+pub struct Record {
+    pub cells: Vec<String>,
+    /// Drawn greyed out, as disabled cron jobs are.
+    pub dimmed: bool,
+    /// Spans drawn after the value of a column, e.g. the warning sign of a cron job.
+    pub marks: Vec<(usize, Span<'static>)>,
+}
+
+/// The selected row of a table of records and the scrollbar that follows it.
+#[derive(Default)]
+pub struct RecordSelection {
+    pub state: TableState,
+    pub scroll_state: ScrollbarState,
+    len: usize,
+}
+
+impl RecordSelection {
+    pub fn selected(&self) -> Option<usize> {
+        self.state.selected().filter(|&i| i < self.len)
+    }
+
+    /// Adapts to a new number of rows, keeping the selected row if it still exists.
+    pub fn set_len(&mut self, len: usize) {
+        self.len = len;
+        self.scroll_state = ScrollbarState::new(len.saturating_sub(1) * RECORD_HEIGHT);
+        let selected = self
+            .state
+            .selected()
+            .unwrap_or(0)
+            .min(len.saturating_sub(1));
+        self.select(selected);
+    }
+
+    pub fn select(&mut self, i: usize) {
+        self.state.select(Some(i));
+        self.scroll_state = self.scroll_state.position(i * RECORD_HEIGHT);
+    }
+
+    pub fn next(&mut self) {
+        if self.len > 0 {
+            let i = match self.state.selected() {
+                Some(i) if i + 1 < self.len => i + 1,
+                _ => 0,
+            };
+            self.select(i);
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if self.len > 0 {
+            let i = match self.state.selected() {
+                Some(i) if i > 0 => i - 1,
+                _ => self.len - 1,
+            };
+            self.select(i);
+        }
+    }
+
+    pub fn first(&mut self) {
+        self.select(0);
+    }
+
+    pub fn last(&mut self) {
+        self.select(self.len.saturating_sub(1));
+    }
+}
+
+/// The part of `area` a popup of the given size takes, in its middle.
+pub fn center(area: Rect, horizontal: Constraint, vertical: Constraint) -> Rect {
+    let [area] = Layout::horizontal([horizontal])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([vertical]).flex(Flex::Center).areas(area);
+    area
+}
 
 pub trait Drawable {
     fn draw_footer(
@@ -55,3 +141,155 @@ pub trait Drawable {
         Ok(())
     }
 }
+
+/// Draws records as the scheduling and user tables look: rows in alternating colours under a bold
+/// header, with every column as wide as its longest value, and a scrollbar.
+pub fn draw_records(
+    frame: &mut Frame,
+    area: Rect,
+    titles: &[&str],
+    records: &[Record],
+    selection: &mut RecordSelection,
+    styles: &TableStyles,
+) {
+    let header = titles
+        .iter()
+        .map(|title| Cell::from(Text::from(format!("\n{}\n", title))))
+        .collect::<Row>()
+        .style(styles.header_style)
+        .height(3);
+
+    let rows = records.iter().enumerate().map(|(i, record)| {
+        let color = if i % 2 == 0 {
+            styles.normal_row_color
+        } else {
+            styles.alt_row_color
+        };
+        let style = if record.dimmed {
+            styles.disabled_row_style
+        } else {
+            styles.row_style
+        };
+        record
+            .cells
+            .iter()
+            .enumerate()
+            .map(|(column, content)| {
+                let mut line = Line::raw(content.as_str());
+                for (_, mark) in record.marks.iter().filter(|(at, _)| *at == column) {
+                    line.push_span(mark.clone());
+                }
+                Cell::from(Text::from(vec![Line::raw(""), line, Line::raw("")]))
+            })
+            .collect::<Row>()
+            .style(style.bg(color))
+            .height(RECORD_HEIGHT as u16)
+    });
+
+    let widths = titles.iter().enumerate().map(|(column, title)| {
+        let longest = records
+            .iter()
+            .filter_map(|record| {
+                let marks = record
+                    .marks
+                    .iter()
+                    .filter(|(at, _)| *at == column)
+                    .map(|(_, mark)| mark.width());
+                record
+                    .cells
+                    .get(column)
+                    .map(|cell| cell.width() + marks.sum::<usize>())
+            })
+            .max()
+            .unwrap_or(0)
+            .max(title.width());
+        #[allow(clippy::cast_possible_truncation)]
+        Constraint::Min(longest as u16 + 2)
+    });
+
+    let bar = " ▌ ";
+    let table = Table::new(rows, widths)
+        .header(header)
+        .row_highlight_style(styles.selected_row_style)
+        .style(
+            styles
+                .row_style
+                .bg(if (records.len() + 1).is_multiple_of(2) {
+                    styles.alt_row_color
+                } else {
+                    styles.normal_row_color
+                }),
+        )
+        .highlight_symbol(Text::from(vec!["".into(), bar.into(), "".into()]))
+        .highlight_spacing(HighlightSpacing::Always);
+
+    frame.render_stateful_widget(table, area, &mut selection.state);
+
+    let scrollbar = Scrollbar::default()
+        .orientation(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .style(styles.scrollbar_style);
+    frame.render_stateful_widget(
+        scrollbar,
+        area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        }),
+        &mut selection.scroll_state,
+    );
+}
+
+/// A dialog asking `question` about the `commands` it shows, with `notes` on the options of the
+/// change and, once the commands failed, what they printed.
+pub fn draw_confirmation(
+    frame: &mut Frame,
+    question: &str,
+    commands: &[String],
+    notes: &[String],
+    failure: Option<&str>,
+    styles: &EditWindowStyles,
+) {
+    let mut lines = vec![
+        Line::raw(""),
+        Line::styled(question.to_string(), styles.title_style),
+        Line::raw(""),
+    ];
+    lines.extend(
+        commands
+            .iter()
+            .map(|command| Line::styled(format!("$ {}", command), styles.valid_input_style)),
+    );
+    if !notes.is_empty() {
+        lines.push(Line::raw(""));
+        lines.extend(notes.iter().map(|note| Line::raw(note.clone())));
+    }
+    if let Some(failure) = failure {
+        lines.push(Line::raw(""));
+        lines.extend(
+            failure
+                .lines()
+                .map(|line| Line::styled(line.to_string(), styles.invalid_input_style)),
+        );
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    let height = lines.len() as u16 + 4;
+    let area = center(
+        frame.area(),
+        Constraint::Percentage(70),
+        Constraint::Length(height),
+    );
+    frame.render_widget(Clear, area);
+    let dialog = Paragraph::new(Text::from(lines))
+        .wrap(Wrap { trim: false })
+        .style(styles.window_style)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick)
+                .border_style(styles.window_border_style)
+                .title(" Confirm "),
+        );
+    frame.render_widget(dialog, area);
+}
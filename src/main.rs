@@ -7,6 +7,7 @@ use crate::app::App;
 mod action;
 mod app;
 mod cli;
+mod command;
 mod components;
 mod config;
 mod draw;
@@ -34,6 +34,7 @@ pub struct TableStyles {
     pub header_style: Style,
     pub selected_row_style: Style,
     pub row_style: Style,
+    pub disabled_row_style: Style,
     pub normal_row_color: Color,
     pub alt_row_color: Color,
     pub scrollbar_style: Style,
@@ -48,6 +49,9 @@ impl TableStyles {
                 .add_modifier(Modifier::BOLD),
             selected_row_style: Style::new().fg(tailwind::GRAY.c300).bg(tailwind::SKY.c950),
             row_style: Style::new().fg(tailwind::GRAY.c200),
+            disabled_row_style: Style::new()
+                .fg(tailwind::GRAY.c500)
+                .add_modifier(Modifier::DIM),
             normal_row_color: tailwind::SLATE.c700,
             alt_row_color: tailwind::SLATE.c600,
             scrollbar_style: Style::new()